@@ -0,0 +1,52 @@
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use rayon::prelude::*;
+use skiplist::SkipList;
+use std::hint::black_box;
+
+fn parallel_sum_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("consume_sum");
+    group.sample_size(10);
+
+    let size = 1_000_000u64;
+    group.throughput(Throughput::Elements(size));
+
+    group.bench_function("sequential", |b| {
+        b.iter_batched(
+            || SkipList::from_sorted((0..size).map(|i| (i, i))),
+            |skip_list| black_box(skip_list.into_iter().map(|(_, v)| v).sum::<u64>()),
+            criterion::BatchSize::LargeInput,
+        );
+    });
+
+    group.bench_function("parallel", |b| {
+        b.iter_batched(
+            || SkipList::from_sorted((0..size).map(|i| (i, i))),
+            |skip_list| black_box(skip_list.into_par_iter().map(|(_, v)| v).sum::<u64>()),
+            criterion::BatchSize::LargeInput,
+        );
+    });
+
+    // The per-entry work is where the parallel split pays off
+    let heavy = |v: u64| (0..64).fold(v, |acc, i| acc.rotate_left(5) ^ i);
+
+    group.bench_function("sequential_heavy", |b| {
+        b.iter_batched(
+            || SkipList::from_sorted((0..size).map(|i| (i, i))),
+            |skip_list| black_box(skip_list.into_iter().map(|(_, v)| heavy(v)).sum::<u64>()),
+            criterion::BatchSize::LargeInput,
+        );
+    });
+
+    group.bench_function("parallel_heavy", |b| {
+        b.iter_batched(
+            || SkipList::from_sorted((0..size).map(|i| (i, i))),
+            |skip_list| black_box(skip_list.into_par_iter().map(|(_, v)| heavy(v)).sum::<u64>()),
+            criterion::BatchSize::LargeInput,
+        );
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, parallel_sum_benchmark);
+criterion_main!(benches);
@@ -1,6 +1,6 @@
 use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
 use rand::prelude::*;
-use skiplist::SkipList;
+use skiplist::{NodePool, SkipList};
 use std::collections::{BTreeMap, LinkedList};
 use std::hint::black_box;
 
@@ -122,6 +122,31 @@ fn get_benchmark(c: &mut Criterion) {
     group.finish();
 }
 
+fn get_batch_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("get_batch");
+
+    let size = 1_000_000;
+    let skip_list = SkipList::from_sorted((0..size).map(|i| (i, i)));
+    let mut rng = StdRng::seed_from_u64(42);
+    let mut keys: Vec<i32> = (0..10_000).map(|_| rng.random_range(0..size)).collect();
+    keys.sort();
+
+    group.throughput(Throughput::Elements(keys.len() as u64));
+
+    group.bench_function("get_batch_sorted", |b| {
+        b.iter(|| black_box(skip_list.get_batch(&keys)));
+    });
+
+    group.bench_function("get_loop", |b| {
+        b.iter(|| {
+            let values: Vec<_> = keys.iter().map(|key| skip_list.get(key)).collect();
+            black_box(values)
+        });
+    });
+
+    group.finish();
+}
+
 fn remove_benchmark(c: &mut Criterion) {
     let mut group = c.benchmark_group("remove");
     
@@ -208,6 +233,191 @@ fn remove_benchmark(c: &mut Criterion) {
     group.finish();
 }
 
+fn bulk_remove_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("bulk_remove");
+    group.sample_size(10);
+
+    let size = 1_000_000;
+    let mut rng = StdRng::seed_from_u64(42);
+    let mut keys: Vec<i32> = (0..10_000).map(|_| rng.random_range(0..size)).collect();
+    keys.sort();
+    keys.dedup();
+
+    group.throughput(Throughput::Elements(keys.len() as u64));
+
+    group.bench_function("bulk_remove", |b| {
+        b.iter_batched(
+            || SkipList::from_sorted((0..size).map(|i| (i, i))),
+            |mut skip_list| {
+                black_box(skip_list.bulk_remove(&keys));
+                skip_list
+            },
+            criterion::BatchSize::LargeInput,
+        );
+    });
+
+    group.bench_function("remove_loop", |b| {
+        b.iter_batched(
+            || SkipList::from_sorted((0..size).map(|i| (i, i))),
+            |mut skip_list| {
+                for key in &keys {
+                    black_box(skip_list.remove(key));
+                }
+                skip_list
+            },
+            criterion::BatchSize::LargeInput,
+        );
+    });
+
+    group.finish();
+}
+
+fn pool_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("build_drain_cycle");
+
+    let mut rng = StdRng::seed_from_u64(42);
+    let keys: Vec<i32> = (0..10_000).map(|_| rng.random()).collect();
+    group.throughput(Throughput::Elements(keys.len() as u64));
+
+    group.bench_function("without_pool", |b| {
+        b.iter(|| {
+            let mut skip_list = SkipList::new();
+            for &key in &keys {
+                skip_list.insert(key, key);
+            }
+            for entry in skip_list {
+                black_box(entry);
+            }
+        });
+    });
+
+    let pool = NodePool::new();
+    group.bench_function("with_pool", |b| {
+        b.iter(|| {
+            let mut skip_list = SkipList::with_pool(&pool);
+            for &key in &keys {
+                skip_list.insert(key, key);
+            }
+            for entry in skip_list {
+                black_box(entry);
+            }
+        });
+    });
+
+    group.finish();
+}
+
+fn clear_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("build_clear_cycle");
+
+    let mut rng = StdRng::seed_from_u64(42);
+    let keys: Vec<i32> = (0..10_000).map(|_| rng.random()).collect();
+    group.throughput(Throughput::Elements(keys.len() as u64));
+
+    let mut skip_list = SkipList::new();
+    group.bench_function("clear", |b| {
+        b.iter(|| {
+            for &key in &keys {
+                skip_list.insert(key, key);
+            }
+            skip_list.clear();
+        });
+    });
+
+    let mut skip_list = SkipList::new();
+    group.bench_function("clear_retaining_capacity", |b| {
+        b.iter(|| {
+            for &key in &keys {
+                skip_list.insert(key, key);
+            }
+            skip_list.clear_retaining_capacity();
+        });
+    });
+
+    group.finish();
+}
+
+fn insert_cursor_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("mostly_increasing_insert");
+
+    // Ascending keys with a small random step, and a jump back every so often
+    let mut rng = StdRng::seed_from_u64(42);
+    let mut key = 0;
+    let keys: Vec<i32> = (0..10_000)
+        .map(|_| {
+            key = if rng.random_ratio(1, 20) {
+                rng.random_range(0..=key)
+            } else {
+                key + rng.random_range(1..4)
+            };
+            key
+        })
+        .collect();
+    group.throughput(Throughput::Elements(keys.len() as u64));
+
+    group.bench_function("insert", |b| {
+        b.iter(|| {
+            let mut skip_list = SkipList::new();
+            for &key in &keys {
+                skip_list.insert(black_box(key), key);
+            }
+            skip_list
+        });
+    });
+
+    group.bench_function("insert_cursor", |b| {
+        b.iter(|| {
+            let mut skip_list = SkipList::new();
+            let mut cursor = skip_list.insert_cursor();
+            for &key in &keys {
+                cursor.insert(black_box(key), key);
+            }
+            drop(cursor);
+            skip_list
+        });
+    });
+
+    group.finish();
+
+    // A run of nearby keys into a list that is already large: a full search
+    // descends all ~17 levels every time, the cursor only the few levels
+    // spanning the distance from the previous key
+    let mut group = c.benchmark_group("ascending_insert_into_large_list");
+    let base = || SkipList::from_sorted((0..200_000).step_by(2).map(|i| (i, i)));
+    let keys: Vec<i32> = (0..10_000).map(|i| 50_001 + 2 * i).collect();
+    group.throughput(Throughput::Elements(keys.len() as u64));
+
+    group.bench_function("insert", |b| {
+        b.iter_batched(
+            base,
+            |mut skip_list| {
+                for &key in &keys {
+                    skip_list.insert(black_box(key), key);
+                }
+                skip_list
+            },
+            criterion::BatchSize::LargeInput,
+        );
+    });
+
+    group.bench_function("insert_cursor", |b| {
+        b.iter_batched(
+            base,
+            |mut skip_list| {
+                let mut cursor = skip_list.insert_cursor();
+                for &key in &keys {
+                    cursor.insert(black_box(key), key);
+                }
+                drop(cursor);
+                skip_list
+            },
+            criterion::BatchSize::LargeInput,
+        );
+    });
+
+    group.finish();
+}
+
 fn iteration_benchmark(c: &mut Criterion) {
     let mut group = c.benchmark_group("iteration");
     
@@ -476,7 +686,12 @@ criterion_group!(
     benches,
     insert_benchmark,
     get_benchmark,
+    get_batch_benchmark,
     remove_benchmark,
+    bulk_remove_benchmark,
+    pool_benchmark,
+    clear_benchmark,
+    insert_cursor_benchmark,
     iteration_benchmark,
     mixed_operations_benchmark,
     sequential_vs_random_benchmark,
@@ -488,7 +703,12 @@ criterion_group!(
     benches,
     insert_benchmark,
     get_benchmark,
+    get_batch_benchmark,
     remove_benchmark,
+    bulk_remove_benchmark,
+    pool_benchmark,
+    clear_benchmark,
+    insert_cursor_benchmark,
     iteration_benchmark,
     mixed_operations_benchmark,
     sequential_vs_random_benchmark
@@ -0,0 +1,169 @@
+use std::fmt;
+
+use rand::RngCore;
+
+use crate::{DEFAULT_PROBABILITY, Key, LevelGenerator, MAX_LEVEL, SkipList, Value};
+
+/// Fluent configuration for a `SkipList`.
+///
+/// `SkipList::new()` is equivalent to `SkipListBuilder::new().build().unwrap()`.
+///
+/// # Examples
+///
+/// ```
+/// use rand::{SeedableRng, rngs::StdRng};
+/// use skiplist::SkipListBuilder;
+///
+/// let mut skip_list = SkipListBuilder::new()
+///     .probability(0.25)
+///     .max_level(16)
+///     .rng(StdRng::seed_from_u64(42))
+///     .expected_len(1000)
+///     .build()
+///     .unwrap();
+///
+/// skip_list.insert(1, "one");
+/// assert_eq!(skip_list.get(&1), Some(&"one"));
+/// ```
+pub struct SkipListBuilder<K: Key, V: Value> {
+    probability: f64,
+    max_level: usize,
+    dynamic_max_level: bool,
+    rng: Option<Box<dyn RngCore>>,
+    expected_len: usize,
+    _marker: std::marker::PhantomData<(K, V)>,
+}
+
+/// Reasons `SkipListBuilder::build` can reject a configuration.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BuildError {
+    /// The probability must lie strictly between 0 and 1.
+    InvalidProbability(f64),
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BuildError::InvalidProbability(p) => {
+                write!(f, "probability must be in (0, 1), got {}", p)
+            }
+        }
+    }
+}
+
+impl std::error::Error for BuildError {}
+
+impl<K: Key, V: Value> SkipListBuilder<K, V> {
+    pub fn new() -> Self {
+        Self {
+            probability: DEFAULT_PROBABILITY,
+            max_level: MAX_LEVEL,
+            dynamic_max_level: false,
+            rng: None,
+            expected_len: 0,
+            _marker: std::marker::PhantomData,
+        }
+    }
+
+    /// Probability that a node is promoted to the next level. Defaults to 0.5.
+    pub fn probability(mut self, probability: f64) -> Self {
+        self.probability = probability;
+        self
+    }
+
+    /// Highest level (0-based) a node may reach. Defaults to 32.
+    pub fn max_level(mut self, max_level: usize) -> Self {
+        self.max_level = max_level;
+        self
+    }
+
+    /// Cap node levels by the current length instead of `max_level`: a node
+    /// joining a list of `len` entries gets at most level
+    /// `ceil(log_{1/p}(len + 1))`. Towers stay short while the list is small
+    /// and can grow past 32 levels once it is large enough. Off by default.
+    ///
+    /// At the default probability of 0.5 the cap only passes 32 beyond 2^32
+    /// entries, so towers taller than the static default need a higher
+    /// probability: at 0.9 a few hundred entries are enough.
+    pub fn dynamic_max_level(mut self, enabled: bool) -> Self {
+        self.dynamic_max_level = enabled;
+        self
+    }
+
+    /// RNG used to draw node levels, e.g. a seeded one for reproducible towers.
+    /// Defaults to the thread-local RNG.
+    pub fn rng<R: RngCore + 'static>(mut self, rng: R) -> Self {
+        self.rng = Some(Box::new(rng));
+        self
+    }
+
+    /// Number of elements the list is expected to hold. Used to size the head
+    /// tower up front; it does not limit the list.
+    pub fn expected_len(mut self, expected_len: usize) -> Self {
+        self.expected_len = expected_len;
+        self
+    }
+
+    pub fn build(self) -> Result<SkipList<K, V>, BuildError> {
+        if !(self.probability > 0.0 && self.probability < 1.0) {
+            return Err(BuildError::InvalidProbability(self.probability));
+        }
+
+        let mut skip_list = SkipList::new();
+
+        if self.expected_len > 0 {
+            // Expected height of a list with `expected_len` elements
+            let height = (self.expected_len as f64)
+                .log(1.0 / self.probability)
+                .ceil() as usize;
+            let height = if self.dynamic_max_level {
+                height + 1
+            } else {
+                height.min(self.max_level) + 1
+            };
+            unsafe { skip_list.head.as_mut() }.forward.reserve(height);
+        }
+
+        skip_list.levels = LevelGenerator {
+            probability: self.probability,
+            max_level: self.max_level,
+            dynamic: self.dynamic_max_level,
+            rng: self.rng,
+        };
+
+        Ok(skip_list)
+    }
+}
+
+impl<K: Key, V: Value> SkipList<K, V> {
+    /// Collect `iter` into a list configured by `config`, like `collect` but
+    /// with a chosen probability, maximum level or RNG. Entries are inserted
+    /// one by one, later ones replacing the values of earlier equal keys.
+    ///
+    /// Time complexity: O(n log n) expected
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::{SkipList, SkipListBuilder};
+    ///
+    /// let config = SkipListBuilder::new().probability(0.25);
+    /// let skip_list = SkipList::from_iter_with((0..100).map(|i| (i, i)), config).unwrap();
+    ///
+    /// assert_eq!(skip_list.len(), 100);
+    /// ```
+    pub fn from_iter_with<I: IntoIterator<Item = (K, V)>>(
+        iter: I,
+        config: SkipListBuilder<K, V>,
+    ) -> Result<Self, BuildError> {
+        let mut skip_list = config.build()?;
+        skip_list.extend(iter);
+        Ok(skip_list)
+    }
+}
+
+impl<K: Key, V: Value> Default for SkipListBuilder<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,180 @@
+use std::fmt;
+
+use crate::{Key, SkipList, Value};
+
+/// Encoding of a key or value for `SkipList::to_bytes`.
+///
+/// The list frames every encoded item with its length, so implementations
+/// only write their own bytes.
+pub trait ToBytes {
+    fn to_bytes(&self, out: &mut Vec<u8>);
+}
+
+/// Decoding of a key or value for `SkipList::from_bytes`.
+///
+/// `bytes` holds exactly what `ToBytes::to_bytes` wrote; return `None` if it
+/// is not a valid encoding.
+pub trait FromBytes: Sized {
+    fn from_bytes(bytes: &[u8]) -> Option<Self>;
+}
+
+macro_rules! impl_int_bytes {
+    ($($ty:ty),*) => {
+        $(
+            impl ToBytes for $ty {
+                fn to_bytes(&self, out: &mut Vec<u8>) {
+                    out.extend_from_slice(&self.to_le_bytes());
+                }
+            }
+
+            impl FromBytes for $ty {
+                fn from_bytes(bytes: &[u8]) -> Option<Self> {
+                    Some(<$ty>::from_le_bytes(bytes.try_into().ok()?))
+                }
+            }
+        )*
+    };
+}
+
+impl_int_bytes!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128);
+
+/// Reasons `SkipList::from_bytes` can reject its input.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DecodeError {
+    /// The input ended in the middle of a length prefix or an item.
+    Truncated,
+    /// `FromBytes` rejected the key or value of the entry at this position.
+    InvalidItem(usize),
+    /// The key at this position is not greater than the one before it.
+    Unsorted(usize),
+    /// Bytes were left over after the last entry.
+    TrailingBytes,
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::Truncated => write!(f, "input is truncated"),
+            DecodeError::InvalidItem(index) => {
+                write!(f, "entry {} has an invalid key or value", index)
+            }
+            DecodeError::Unsorted(index) => {
+                write!(f, "entry {} is not in strictly ascending key order", index)
+            }
+            DecodeError::TrailingBytes => write!(f, "unexpected bytes after the last entry"),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+/// Reads the length-prefixed layout written by `SkipList::to_bytes`.
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], DecodeError> {
+        if self.bytes.len() < len {
+            return Err(DecodeError::Truncated);
+        }
+        let (head, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Ok(head)
+    }
+
+    fn len_prefix(&mut self) -> Result<usize, DecodeError> {
+        let bytes = self.take(8)?;
+        let len = u64::from_le_bytes(bytes.try_into().unwrap());
+        usize::try_from(len).map_err(|_| DecodeError::Truncated)
+    }
+
+    fn item(&mut self) -> Result<&'a [u8], DecodeError> {
+        let len = self.len_prefix()?;
+        self.take(len)
+    }
+}
+
+/// Append `item` to `out` behind a placeholder length prefix, then fill the
+/// prefix in once the encoded length is known.
+fn write_item<T: ToBytes>(out: &mut Vec<u8>, item: &T) {
+    let start = out.len();
+    out.extend_from_slice(&[0; 8]);
+    item.to_bytes(out);
+    let len = (out.len() - start - 8) as u64;
+    out[start..start + 8].copy_from_slice(&len.to_le_bytes());
+}
+
+impl<K: Key, V: Value> SkipList<K, V> {
+    /// Encode the list into a flat byte buffer.
+    ///
+    /// The layout is the entry count followed by each key and value in sorted
+    /// order, every one of them prefixed with its encoded length. All lengths
+    /// are little-endian `u64`s.
+    ///
+    /// Time complexity: O(n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::SkipList;
+    ///
+    /// let skip_list = SkipList::from_sorted([(1u32, 10u64), (2, 20)]);
+    /// let bytes = skip_list.to_bytes();
+    ///
+    /// let decoded = SkipList::<u32, u64>::from_bytes(&bytes).unwrap();
+    /// assert_eq!(decoded.get(&2), Some(&20));
+    /// ```
+    pub fn to_bytes(&self) -> Vec<u8>
+    where
+        K: ToBytes,
+        V: ToBytes,
+    {
+        let mut out = Vec::new();
+        out.extend_from_slice(&(self.len as u64).to_le_bytes());
+
+        for (key, value) in self.iter() {
+            write_item(&mut out, key);
+            write_item(&mut out, value);
+        }
+
+        out
+    }
+
+    /// Decode a list written by `to_bytes`.
+    ///
+    /// The keys must be strictly ascending, which they are for any buffer
+    /// produced by `to_bytes`; the list is then rebuilt with `from_sorted`.
+    ///
+    /// Time complexity: O(n) expected
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DecodeError>
+    where
+        K: FromBytes,
+        V: FromBytes,
+    {
+        let mut reader = Reader { bytes };
+        let len = reader.len_prefix()?;
+
+        // Every entry takes at least its two length prefixes
+        if len > reader.bytes.len() / 16 {
+            return Err(DecodeError::Truncated);
+        }
+
+        let mut entries: Vec<(K, V)> = Vec::with_capacity(len);
+        for index in 0..len {
+            let key = K::from_bytes(reader.item()?).ok_or(DecodeError::InvalidItem(index))?;
+            let value = V::from_bytes(reader.item()?).ok_or(DecodeError::InvalidItem(index))?;
+
+            if entries.last().is_some_and(|(last, _)| last >= &key) {
+                return Err(DecodeError::Unsorted(index));
+            }
+            entries.push((key, value));
+        }
+
+        if !reader.bytes.is_empty() {
+            return Err(DecodeError::TrailingBytes);
+        }
+
+        Ok(Self::from_sorted(entries))
+    }
+}
@@ -0,0 +1,306 @@
+use std::borrow::Borrow;
+
+use crate::{Key, NodePtr, SkipList, Value};
+
+/// A read-only position in a skip list that can step along level 0 and jump
+/// anywhere with `seek`.
+///
+/// A cursor either points at an entry or past the end, in which case
+/// `current` returns `None`.
+pub struct Cursor<'a, K: Key, V: Value> {
+    skip_list: &'a SkipList<K, V>,
+    ptr: NodePtr<K, V>,
+    rank: usize,
+}
+
+impl<'a, K: Key, V: Value> Cursor<'a, K, V> {
+    /// The entry under the cursor, or `None` past the end.
+    pub fn current(&self) -> Option<(&'a K, &'a V)> {
+        self.entry(self.ptr)
+    }
+
+    /// 0-based position of the cursor, equal to the list's length past the
+    /// end.
+    pub fn rank(&self) -> usize {
+        self.rank
+    }
+
+    /// The entry right after the cursor, without moving.
+    pub fn peek_next(&self) -> Option<(&'a K, &'a V)> {
+        if self.skip_list.is_tail(self.ptr) {
+            return None;
+        }
+
+        self.entry(unsafe { self.ptr.as_ref() }.forward[0].ptr)
+    }
+
+    /// Step to the next entry. Does nothing once past the end.
+    pub fn move_next(&mut self) {
+        if !self.skip_list.is_tail(self.ptr) {
+            self.ptr = unsafe { self.ptr.as_ref() }.forward[0].ptr;
+            self.rank += 1;
+        }
+    }
+
+    /// Step to the previous entry; from past the end, that is the last entry.
+    /// Does nothing at the first entry.
+    ///
+    /// Nodes have no back links, so this is a positional lookup rather than a
+    /// single step.
+    ///
+    /// Time complexity: O(log n) expected
+    pub fn move_prev(&mut self) {
+        if let Some(ptr) = self
+            .rank
+            .checked_sub(1)
+            .and_then(|rank| self.skip_list.node_at(rank))
+        {
+            self.ptr = ptr;
+            self.rank -= 1;
+        }
+    }
+
+    /// Reposition at the first key not less than `key`, which may be before
+    /// or after the current position.
+    ///
+    /// Time complexity: O(log n) expected
+    pub fn seek<Q>(&mut self, key: &Q)
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        (self.ptr, self.rank) = self.skip_list.lower_bound_node(key);
+    }
+
+    fn entry(&self, ptr: NodePtr<K, V>) -> Option<(&'a K, &'a V)> {
+        if self.skip_list.is_tail(ptr) {
+            return None;
+        }
+
+        let node = unsafe { ptr.as_ref() };
+        Some((node.key(), node.value()))
+    }
+}
+
+impl<K: Key, V: Value> SkipList<K, V> {
+    /// Get a cursor positioned at the first key not less than `key`.
+    ///
+    /// Time complexity: O(log n) expected
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::SkipList;
+    ///
+    /// let skip_list = SkipList::from_sorted((0..10).map(|i| (i * 10, i)));
+    ///
+    /// let mut cursor = skip_list.cursor_at(&25);
+    /// assert_eq!(cursor.current(), Some((&30, &3)));
+    /// cursor.move_next();
+    /// assert_eq!(cursor.current(), Some((&40, &4)));
+    /// ```
+    pub fn cursor_at<Q>(&self, key: &Q) -> Cursor<'_, K, V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.lower_bound(key)
+    }
+
+    /// Get a cursor at the first key not less than `key`, or past the end if
+    /// there is none, like C++ `std::map::lower_bound`.
+    ///
+    /// Time complexity: O(log n) expected
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::SkipList;
+    ///
+    /// let skip_list = SkipList::from_sorted([(3, 'a'), (5, 'b'), (7, 'c')]);
+    ///
+    /// let mut cursor = skip_list.lower_bound(&5);
+    /// assert_eq!(cursor.current(), Some((&5, &'b')));
+    /// assert_eq!(cursor.rank(), 1);
+    /// cursor.move_prev();
+    /// assert_eq!(cursor.current(), Some((&3, &'a')));
+    /// ```
+    pub fn lower_bound<Q>(&self, key: &Q) -> Cursor<'_, K, V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let (ptr, rank) = self.lower_bound_node(key);
+        Cursor {
+            skip_list: self,
+            ptr,
+            rank,
+        }
+    }
+
+    /// Get a cursor at the first key greater than `key`, or past the end if
+    /// there is none, like C++ `std::map::upper_bound`.
+    ///
+    /// Time complexity: O(log n) expected
+    pub fn upper_bound<Q>(&self, key: &Q) -> Cursor<'_, K, V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let (ptr, rank) = self.upper_bound_node(key);
+        Cursor {
+            skip_list: self,
+            ptr,
+            rank,
+        }
+    }
+
+    /// Get a cursor for inserting and looking up keys in mostly ascending
+    /// order, resuming each search where the previous one ended.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::SkipList;
+    ///
+    /// let mut skip_list = SkipList::new();
+    /// let mut cursor = skip_list.insert_cursor();
+    /// for i in 0..100 {
+    ///     cursor.insert(i, i * 2);
+    /// }
+    /// *cursor.get_or_insert_with(50, || 0) += 1;
+    /// drop(cursor);
+    ///
+    /// assert_eq!(skip_list.len(), 100);
+    /// assert_eq!(skip_list.get(&50), Some(&101));
+    /// ```
+    pub fn insert_cursor(&mut self) -> InsertCursor<'_, K, V> {
+        InsertCursor {
+            update: vec![self.head; self.level + 1],
+            steps: vec![0; self.level + 1],
+            skip_list: self,
+        }
+    }
+}
+
+/// A writing cursor that remembers the update frontier of its last search, so
+/// a run of increasing keys does not descend from the head every time.
+///
+/// For a key greater than the one last searched for, the search continues
+/// forward from the remembered frontier; for a smaller key it falls back to a
+/// full descent. Either way the result is the same as calling the list's own
+/// methods, only the cost differs.
+///
+/// Obtained from `SkipList::insert_cursor`.
+pub struct InsertCursor<'a, K: Key, V: Value> {
+    skip_list: &'a mut SkipList<K, V>,
+    update: Vec<NodePtr<K, V>>,
+    steps: Vec<usize>,
+}
+
+impl<K: Key, V: Value> InsertCursor<'_, K, V> {
+    /// Insert a key-value pair like `SkipList::insert`, returning the old
+    /// value if the key was present.
+    ///
+    /// Time complexity: O(log d) expected, d being the distance from the
+    /// previous key, for a key greater than it; O(log n) expected otherwise
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        match self.seek(&key) {
+            Some(mut node) => Some(std::mem::replace(
+                unsafe { node.as_mut() }.value_mut(),
+                value,
+            )),
+            None => {
+                self.link(key, value);
+                None
+            }
+        }
+    }
+
+    /// Get the value under `key`, first inserting the value `f` computes if
+    /// the key is absent.
+    ///
+    /// Time complexity: same as `insert`
+    pub fn get_or_insert_with<F: FnOnce() -> V>(&mut self, key: K, f: F) -> &mut V {
+        let mut node = match self.seek(&key) {
+            Some(node) => node,
+            None => self.link(key, f()),
+        };
+        unsafe { node.as_mut() }.value_mut()
+    }
+
+    /// Look up `key` like `SkipList::get`, moving the cursor to it.
+    ///
+    /// Time complexity: same as `insert`
+    pub fn get<Q>(&mut self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.seek(key).map(|node| unsafe { node.as_ref() }.value())
+    }
+
+    /// Move the frontier to `key` and return its node if present.
+    fn seek<Q>(&mut self, key: &Q) -> Option<NodePtr<K, V>>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let skip_list = &*self.skip_list;
+
+        // The frontier can only be reused if every node on it is still before
+        // `key`; `update[0]` is the furthest along
+        let last = self.update[0];
+        if skip_list.is_head(last) || unsafe { last.as_ref() }.key().borrow() < key {
+            let before_key = |node: NodePtr<K, V>| {
+                !skip_list.is_tail(node) && unsafe { node.as_ref() }.key().borrow() < key
+            };
+
+            // Climb until the frontier's next link on a level passes `key`:
+            // that level and every one above it stay as they are, and only
+            // the ones below move, so the search costs O(log d) instead of
+            // O(log n)
+            let mut top = 0;
+            while top <= skip_list.level
+                && before_key(unsafe { self.update[top].as_ref() }.forward[top].ptr)
+            {
+                top += 1;
+            }
+
+            let (mut cur, mut step) = if top <= skip_list.level {
+                (self.update[top], self.steps[top])
+            } else {
+                (skip_list.head, 0)
+            };
+            for i in (0..top).rev() {
+                if self.steps[i] > step {
+                    (cur, step) = (self.update[i], self.steps[i]);
+                }
+                loop {
+                    let cur_node_ref = unsafe { cur.as_ref() };
+                    let next = cur_node_ref.forward[i].ptr;
+
+                    if !before_key(next) {
+                        break;
+                    }
+                    step += cur_node_ref.forward[i].span;
+                    cur = next;
+                }
+                self.update[i] = cur;
+                self.steps[i] = step;
+            }
+        } else {
+            (self.update, self.steps) = skip_list.find_update(key);
+        }
+
+        let next = unsafe { self.update[0].as_ref() }.forward[0].ptr;
+        (!skip_list.is_tail(next) && unsafe { next.as_ref() }.key().borrow() == key).then_some(next)
+    }
+
+    /// Link a new node right after the frontier left by `seek`.
+    fn link(&mut self, key: K, value: V) -> NodePtr<K, V> {
+        let level = self.skip_list.rand_level();
+        self.skip_list
+            .link_node(&mut self.update, &mut self.steps, key, value, level)
+    }
+}
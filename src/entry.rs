@@ -0,0 +1,271 @@
+use std::ops::AddAssign;
+
+use crate::{Key, NodePtr, SkipList, Value};
+
+/// A view into a single entry of a skip list, which may either be vacant or
+/// occupied. Obtained from `SkipList::entry`.
+pub enum Entry<'a, K: Key, V: Value> {
+    Occupied(OccupiedEntry<'a, K, V>),
+    Vacant(VacantEntry<'a, K, V>),
+}
+
+/// An entry whose key is present in the list.
+///
+/// It keeps the update frontier from the descent that found it, so `remove`
+/// only has to splice the node out without searching again.
+pub struct OccupiedEntry<'a, K: Key, V: Value> {
+    skip_list: &'a mut SkipList<K, V>,
+    update: Vec<NodePtr<K, V>>,
+    node: NodePtr<K, V>,
+    rank: usize,
+}
+
+/// An entry whose key is absent from the list.
+///
+/// It keeps the update frontier and positions from the descent, so `insert`
+/// only has to link the new node in.
+pub struct VacantEntry<'a, K: Key, V: Value> {
+    skip_list: &'a mut SkipList<K, V>,
+    key: K,
+    update: Vec<NodePtr<K, V>>,
+    steps: Vec<usize>,
+}
+
+impl<K: Key, V: Value> SkipList<K, V> {
+    /// Get the entry for `key` for in-place manipulation.
+    ///
+    /// Time complexity: O(log n) expected
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::SkipList;
+    ///
+    /// let mut counts = SkipList::new();
+    /// for word in ["a", "b", "a"] {
+    ///     *counts.entry(word).or_insert(0) += 1;
+    /// }
+    ///
+    /// assert_eq!(counts.get(&"a"), Some(&2));
+    /// assert_eq!(counts.get(&"b"), Some(&1));
+    /// ```
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V> {
+        let (update, steps) = self.find_update(&key);
+
+        let next = unsafe { update[0].as_ref() }.forward[0].ptr;
+
+        if !self.is_tail(next) && unsafe { next.as_ref() }.key() == &key {
+            Entry::Occupied(OccupiedEntry {
+                skip_list: self,
+                rank: steps[0],
+                update,
+                node: next,
+            })
+        } else {
+            Entry::Vacant(VacantEntry {
+                skip_list: self,
+                key,
+                update,
+                steps,
+            })
+        }
+    }
+
+    /// Get the entry at 0-based position `index`, or `None` if it is out of
+    /// bounds.
+    ///
+    /// The span-guided descent that finds it records the same frontier as
+    /// `entry`, so removing the entry does not search again.
+    ///
+    /// Time complexity: O(log n) expected
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::SkipList;
+    ///
+    /// let mut skip_list = SkipList::from_sorted((0..5).map(|i| (i * 10, i)));
+    ///
+    /// *skip_list.index_entry(1).unwrap().get_mut() += 100;
+    /// assert_eq!(skip_list.index_entry(3).unwrap().remove_entry(), (30, 3));
+    ///
+    /// let entries: Vec<_> = skip_list.iter().map(|(&k, &v)| (k, v)).collect();
+    /// assert_eq!(entries, vec![(0, 0), (10, 101), (20, 2), (40, 4)]);
+    /// ```
+    pub fn index_entry(&mut self, index: usize) -> Option<OccupiedEntry<'_, K, V>> {
+        if index >= self.len {
+            return None;
+        }
+
+        // The last node before position `index + 1` on every level
+        let (update, _) = self.position_frontier(index);
+        let node = unsafe { update[0].as_ref() }.forward[0].ptr;
+
+        Some(OccupiedEntry {
+            skip_list: self,
+            update,
+            node,
+            rank: index,
+        })
+    }
+
+    /// Add one to the value under `key`, starting from `V::default()` if the
+    /// key is absent, and return the updated count.
+    ///
+    /// Sugar for `*entry(key).or_default() += 1`, in a single descent. The
+    /// increment is `V::from(1u8)`, so any unsigned or signed counter type
+    /// wider than a byte works, as well as `u8` and floats.
+    ///
+    /// Time complexity: O(log n) expected
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::SkipList;
+    ///
+    /// let mut counts = SkipList::new();
+    /// for word in ["a", "b", "a"] {
+    ///     counts.count_up(word);
+    /// }
+    ///
+    /// assert_eq!(counts.get(&"a"), Some(&2usize));
+    /// ```
+    pub fn count_up(&mut self, key: K) -> &mut V
+    where
+        V: AddAssign + From<u8> + Default,
+    {
+        let count = self.entry(key).or_default();
+        *count += V::from(1);
+        count
+    }
+}
+
+impl<'a, K: Key, V: Value> Entry<'a, K, V> {
+    pub fn key(&self) -> &K {
+        match self {
+            Entry::Occupied(entry) => entry.key(),
+            Entry::Vacant(entry) => entry.key(),
+        }
+    }
+
+    /// 0-based position the entry occupies, or will occupy once inserted.
+    pub fn rank(&self) -> usize {
+        match self {
+            Entry::Occupied(entry) => entry.rank(),
+            Entry::Vacant(entry) => entry.rank(),
+        }
+    }
+
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(default),
+        }
+    }
+
+    pub fn or_insert_with<F: FnOnce() -> V>(self, default: F) -> &'a mut V {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(default()),
+        }
+    }
+
+    /// Like `or_insert_with`, but the default is computed from the key.
+    pub fn or_insert_with_key<F: FnOnce(&K) -> V>(self, default: F) -> &'a mut V {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert_with_key(default),
+        }
+    }
+
+    pub fn or_default(self) -> &'a mut V
+    where
+        V: Default,
+    {
+        self.or_insert_with(V::default)
+    }
+
+    pub fn and_modify<F: FnOnce(&mut V)>(self, f: F) -> Self {
+        match self {
+            Entry::Occupied(mut entry) => {
+                f(entry.get_mut());
+                Entry::Occupied(entry)
+            }
+            Entry::Vacant(entry) => Entry::Vacant(entry),
+        }
+    }
+}
+
+impl<'a, K: Key, V: Value> OccupiedEntry<'a, K, V> {
+    pub fn key(&self) -> &K {
+        unsafe { self.node.as_ref() }.key()
+    }
+
+    pub fn get(&self) -> &V {
+        unsafe { self.node.as_ref() }.value()
+    }
+
+    pub fn get_mut(&mut self) -> &mut V {
+        unsafe { self.node.as_mut() }.value_mut()
+    }
+
+    /// 0-based position of the entry, taken from the descent that found it.
+    pub fn rank(&self) -> usize {
+        self.rank
+    }
+
+    pub fn into_mut(mut self) -> &'a mut V {
+        unsafe { self.node.as_mut() }.value_mut()
+    }
+
+    /// Replace the value of the entry, returning the old one.
+    pub fn insert(&mut self, value: V) -> V {
+        std::mem::replace(self.get_mut(), value)
+    }
+
+    /// Remove the entry from the list, returning its value.
+    ///
+    /// Time complexity: O(log n) expected, without searching again
+    pub fn remove(self) -> V {
+        self.remove_entry().1
+    }
+
+    /// Remove the entry from the list, returning its key and value.
+    pub fn remove_entry(self) -> (K, V) {
+        self.skip_list.unlink_node(&self.update, self.node)
+    }
+}
+
+impl<'a, K: Key, V: Value> VacantEntry<'a, K, V> {
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    /// 0-based position the entry will occupy once inserted, taken from the
+    /// descent that found it.
+    pub fn rank(&self) -> usize {
+        self.steps[0]
+    }
+
+    /// Take back ownership of the key without inserting anything.
+    pub fn into_key(self) -> K {
+        self.key
+    }
+
+    /// Insert `value` under the entry's key, returning a reference to it.
+    pub fn insert(mut self, value: V) -> &'a mut V {
+        let level = self.skip_list.rand_level();
+        let mut node =
+            self.skip_list
+                .link_node(&mut self.update, &mut self.steps, self.key, value, level);
+
+        unsafe { node.as_mut() }.value_mut()
+    }
+
+    /// Insert the value `f` computes from the entry's key, returning a
+    /// reference to it.
+    pub fn insert_with_key<F: FnOnce(&K) -> V>(self, f: F) -> &'a mut V {
+        let value = f(&self.key);
+        self.insert(value)
+    }
+}
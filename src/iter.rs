@@ -1,54 +1,204 @@
-use std::mem::ManuallyDrop;
+use std::{
+    borrow::Borrow,
+    ops::{Bound, RangeBounds},
+};
 
 use crate::{Key, NodePtr, SkipList, Value};
 
+/// Where a key was found by `SkipList::merge_join`, with the entries
+/// holding it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergeSide<'a, K, V> {
+    /// The key is in both lists; the entry of `self` comes first.
+    Both((&'a K, &'a V), (&'a K, &'a V)),
+    /// The key is only in `self`.
+    Left((&'a K, &'a V)),
+    /// The key is only in `other`.
+    Right((&'a K, &'a V)),
+}
+
+/// Owning iterator, created by `SkipList::into_iter`.
+///
+/// Each entry is unlinked from the list as it is yielded; whatever is left
+/// is freed by the list's own `Drop` when the iterator is dropped.
 pub struct SkipListIntoIter<K: Key, V: Value> {
-    skip_list: ManuallyDrop<SkipList<K, V>>,
-    ptr: NodePtr<K, V>,
+    skip_list: SkipList<K, V>,
 }
 
 impl<K: Key, V: Value> Iterator for SkipListIntoIter<K, V> {
     type Item = (K, V);
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.skip_list.is_tail(self.ptr) {
+        self.skip_list.take_first_for_teardown()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.skip_list.len, Some(self.skip_list.len))
+    }
+}
+
+impl<K: Key, V: Value> ExactSizeIterator for SkipListIntoIter<K, V> {}
+
+impl<K: Key, V: Value> SkipListIntoIter<K, V> {
+    /// The entry the next call to `next` will return, without consuming it.
+    pub fn peek(&self) -> Option<(&K, &V)> {
+        self.peek_nth(0)
+    }
+
+    /// The entry `n` places ahead (0 being the next one), without consuming
+    /// anything.
+    ///
+    /// Entries already yielded are unlinked from level 0 only, so the upper
+    /// levels can no longer be followed and this walks level 0.
+    ///
+    /// Time complexity: O(n)
+    pub fn peek_nth(&self, n: usize) -> Option<(&K, &V)> {
+        if n >= self.skip_list.len {
             return None;
         }
 
-        let next = unsafe { self.ptr.as_ref() }.forward[0].ptr;
+        let mut cur = unsafe { self.skip_list.head.as_ref() }.forward[0].ptr;
+        for _ in 0..n {
+            cur = unsafe { cur.as_ref() }.forward[0].ptr;
+        }
+
+        let node = unsafe { cur.as_ref() };
+        Some((node.key(), node.value()))
+    }
+}
 
-        let node = unsafe { Box::from_raw(self.ptr.as_ptr()) };
-        let key = unsafe { node.key.assume_init() };
-        let value = unsafe { node.value.assume_init() };
+/// Owning iterator in descending key order, created by
+/// `SkipList::into_iter_rev`.
+///
+/// Holds the level 0 chain as a `Vec` of node pointers. Each yielded node is
+/// first cut off the end of the chain, so whatever has not been yielded is
+/// still a valid level 0 prefix that the list's own `Drop` frees.
+pub struct SkipListIntoIterRev<K: Key, V: Value> {
+    skip_list: SkipList<K, V>,
+    nodes: Vec<NodePtr<K, V>>,
+}
 
-        self.ptr = next;
+impl<K: Key, V: Value> Iterator for SkipListIntoIterRev<K, V> {
+    type Item = (K, V);
 
-        Some((key, value))
+    fn next(&mut self) -> Option<Self::Item> {
+        let last = self.nodes.pop()?;
+        let mut before = self.nodes.last().copied().unwrap_or(self.skip_list.head);
+
+        unsafe { before.as_mut() }.forward[0].ptr = self.skip_list.tail;
+        self.skip_list.len -= 1;
+        Some(self.skip_list.free_node(last))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.nodes.len(), Some(self.nodes.len()))
     }
 }
 
+impl<K: Key, V: Value> ExactSizeIterator for SkipListIntoIterRev<K, V> {}
+
 impl<K: Key, V: Value> IntoIterator for SkipList<K, V> {
     type IntoIter = SkipListIntoIter<K, V>;
     type Item = (K, V);
 
     fn into_iter(self) -> Self::IntoIter {
-        let first = unsafe { self.head.as_ref() }.forward[0].ptr;
+        SkipListIntoIter { skip_list: self }
+    }
+}
 
-        SkipListIntoIter {
-            skip_list: ManuallyDrop::new(self),
-            ptr: first,
+impl<K: Key, V: Value> SkipList<K, V> {
+    /// Consume the list in descending key order.
+    ///
+    /// Nodes have no back links, so one forward pass first records a pointer
+    /// to every node, O(n) extra space; each node is then freed as its entry
+    /// is yielded. Dropping the iterator early frees the rest.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::SkipList;
+    ///
+    /// let skip_list = SkipList::from_sorted([(1, 'a'), (2, 'b'), (3, 'c')]);
+    /// let keys: Vec<_> = skip_list.into_iter_rev().map(|(k, _)| k).collect();
+    ///
+    /// assert_eq!(keys, [3, 2, 1]);
+    /// ```
+    pub fn into_iter_rev(self) -> SkipListIntoIterRev<K, V> {
+        let mut nodes = Vec::with_capacity(self.len);
+        let mut cur = unsafe { self.head.as_ref() }.forward[0].ptr;
+        while !self.is_tail(cur) {
+            nodes.push(cur);
+            cur = unsafe { cur.as_ref() }.forward[0].ptr;
+        }
+
+        SkipListIntoIterRev {
+            skip_list: self,
+            nodes,
         }
     }
-}
 
-impl<K: Key, V: Value> Drop for SkipListIntoIter<K, V> {
-    fn drop(&mut self) {
-        for _ in &mut *self {}
+    /// Consume the list into a `Vec` of its entries in sorted order.
+    ///
+    /// The `Vec` is allocated once with the exact length, and every node is
+    /// freed as soon as its entry has been moved out.
+    pub fn into_sorted_vec(self) -> Vec<(K, V)> {
+        let mut entries = Vec::with_capacity(self.len);
+        entries.extend(self);
+        entries
+    }
 
-        unsafe {
-            let _ = Box::from_raw(self.skip_list.head.as_ptr());
-            let _ = Box::from_raw(self.skip_list.tail.as_ptr());
+    /// Consume the list, passing every entry through `f` and building a new
+    /// list from the entries it returns.
+    ///
+    /// `f` may change keys, so the results are sorted before the list is
+    /// built with `from_sorted`; when two results share a key, the one from
+    /// the later entry wins. The new list keeps the level settings and the
+    /// auto-shrink setting, but not a custom RNG or the node pool, whose
+    /// value type differs.
+    ///
+    /// Time complexity: O(n) expected if `f` keeps the keys in order, O(n log
+    /// n) otherwise
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::SkipList;
+    ///
+    /// let skip_list = SkipList::from_sorted((0..6).map(|i| (i, i * 10)));
+    /// let evens = skip_list.filter_map_into(|k, v| (k % 2 == 0).then(|| (k, v.to_string())));
+    ///
+    /// assert_eq!(evens.len(), 3);
+    /// assert_eq!(evens.get(&4), Some(&"40".to_string()));
+    /// ```
+    pub fn filter_map_into<W, F>(self, mut f: F) -> SkipList<K, W>
+    where
+        F: FnMut(K, V) -> Option<(K, W)>,
+    {
+        let levels = self.levels.unseeded();
+        let auto_shrink = self.auto_shrink;
+
+        let mut entries: Vec<(K, W)> = self
+            .into_iter()
+            .filter_map(|(key, value)| f(key, value))
+            .collect();
+
+        if !entries.is_sorted_by(|a, b| a.0 < b.0) {
+            // Stable, so equal keys stay in entry order and the last one is kept
+            entries.sort_by(|a, b| a.0.cmp(&b.0));
+            entries.dedup_by(|later, earlier| {
+                if later.0 == earlier.0 {
+                    std::mem::swap(later, earlier);
+                    true
+                } else {
+                    false
+                }
+            });
         }
+
+        let mut skip_list = SkipList::from_sorted(entries);
+        skip_list.levels = levels;
+        skip_list.auto_shrink = auto_shrink;
+        skip_list
     }
 }
 
@@ -74,6 +224,78 @@ impl<'a, K: Key, V: Value> Iterator for SkipListIter<'a, K, V> {
 
         Some((key, value))
     }
+
+    // `try_fold` itself cannot be overridden on stable Rust (the `Try` trait
+    // is unstable); its default already stops at the first short-circuit. The
+    // overrides below walk level 0 directly instead of going through `next`.
+
+    fn fold<B, F>(mut self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        let mut acc = init;
+        while !self.skip_list_ref.is_tail(self.ptr) {
+            let node = unsafe { self.ptr.as_ref() };
+            acc = f(acc, (node.key(), node.value()));
+            self.ptr = node.forward[0].ptr;
+        }
+        acc
+    }
+
+    /// Skips ahead along the towers of the nodes it passes instead of calling
+    /// `next` `n` times: from the current node it climbs as high as that
+    /// node's tower goes, advances while the spans do not overshoot, and
+    /// descends otherwise, like a search started from the current node.
+    ///
+    /// Time complexity: O(log n) expected, which also speeds up `step_by`
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        if self.skip_list_ref.is_tail(self.ptr) {
+            return None;
+        }
+
+        let mut cur = self.ptr;
+        let mut skipped = 0;
+        // Highest level worth trying; lowered whenever a level overshoots
+        let mut ceiling = self.skip_list_ref.level;
+        let mut level = unsafe { cur.as_ref() }.level.min(ceiling);
+
+        while skipped < n {
+            let forward_ptr = unsafe { cur.as_ref() }.forward[level];
+            if !self.skip_list_ref.is_tail(forward_ptr.ptr) && skipped + forward_ptr.span <= n {
+                skipped += forward_ptr.span;
+                cur = forward_ptr.ptr;
+                level = unsafe { cur.as_ref() }.level.min(ceiling);
+            } else if level > 0 {
+                ceiling = level - 1;
+                level = ceiling;
+            } else {
+                // Fewer than `n` entries are left
+                self.ptr = forward_ptr.ptr;
+                return None;
+            }
+        }
+
+        self.ptr = cur;
+        self.next()
+    }
+
+    /// Stops at the first match and leaves the iterator right after it, so a
+    /// following `next` continues from there.
+    fn find<P>(&mut self, mut predicate: P) -> Option<Self::Item>
+    where
+        P: FnMut(&Self::Item) -> bool,
+    {
+        while !self.skip_list_ref.is_tail(self.ptr) {
+            let node = unsafe { self.ptr.as_ref() };
+            self.ptr = node.forward[0].ptr;
+
+            let item = (node.key(), node.value());
+            if predicate(&item) {
+                return Some(item);
+            }
+        }
+        None
+    }
 }
 
 impl<'a, K: Key, V: Value> IntoIterator for &'a SkipList<K, V> {
@@ -99,6 +321,568 @@ impl<'a, K: Key, V: Value> SkipList<K, V> {
             ptr: first,
         }
     }
+
+    /// Iterate over `(position, &key)` pairs in sorted order.
+    ///
+    /// Positions are 0-based and match the indices accepted by `index`.
+    pub fn enumerate_keys(&'a self) -> impl Iterator<Item = (usize, &'a K)> {
+        self.iter().map(|(key, _)| key).enumerate()
+    }
+
+    /// All keys in sorted order, so that `index[i]` is the key `index(i)`
+    /// returns, but in O(1) once built. The inverse direction, from key to
+    /// position, is `rank`.
+    ///
+    /// Time complexity: O(n)
+    pub fn build_position_index(&'a self) -> Vec<&'a K> {
+        let mut index = Vec::with_capacity(self.len);
+        index.extend(self.iter().map(|(key, _)| key));
+        index
+    }
+
+    /// Iterate over the entries in sorted order in groups of `size`, like
+    /// `slice::chunks`: every group holds `size` entries except possibly the
+    /// last one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::SkipList;
+    ///
+    /// let skip_list = SkipList::from_sorted((0..5).map(|i| (i, i * 10)));
+    /// let mut chunks = skip_list.chunks(2);
+    ///
+    /// assert_eq!(chunks.next(), Some(vec![(&0, &0), (&1, &10)]));
+    /// assert_eq!(chunks.nth(1), Some(vec![(&4, &40)]));
+    /// assert_eq!(chunks.next(), None);
+    /// ```
+    pub fn chunks(&'a self, size: usize) -> impl Iterator<Item = Vec<(&'a K, &'a V)>> {
+        assert!(size > 0, "chunk size must be non-zero");
+
+        let mut entries = self.iter();
+        std::iter::from_fn(move || {
+            let chunk: Vec<_> = entries.by_ref().take(size).collect();
+            (!chunk.is_empty()).then_some(chunk)
+        })
+    }
+
+    /// Entries of `self` whose keys also appear in `other`, in sorted order.
+    ///
+    /// Both level-0 chains are merged in one pass, without any lookups.
+    ///
+    /// Time complexity: O(n + m)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::SkipList;
+    ///
+    /// let a = SkipList::from_sorted([(1, 'a'), (2, 'b'), (3, 'c')]);
+    /// let b = SkipList::from_sorted([(2, 'x'), (3, 'y'), (4, 'z')]);
+    ///
+    /// let both: Vec<_> = a.intersection(&b).map(|(&k, &v)| (k, v)).collect();
+    /// assert_eq!(both, vec![(2, 'b'), (3, 'c')]);
+    /// ```
+    pub fn intersection(
+        &'a self,
+        other: &'a SkipList<K, V>,
+    ) -> impl Iterator<Item = (&'a K, &'a V)> {
+        let mut right = other.iter().peekable();
+
+        self.iter().filter(move |(key, _)| {
+            while right.next_if(|(other_key, _)| other_key < key).is_some() {}
+            right.peek().is_some_and(|(other_key, _)| other_key == key)
+        })
+    }
+
+    /// Entries of `self` whose keys do not appear in `other`, in sorted order.
+    ///
+    /// Both level-0 chains are merged in one pass, without any lookups.
+    ///
+    /// Time complexity: O(n + m)
+    pub fn difference(&'a self, other: &'a SkipList<K, V>) -> impl Iterator<Item = (&'a K, &'a V)> {
+        let mut right = other.iter().peekable();
+
+        self.iter().filter(move |(key, _)| {
+            while right.next_if(|(other_key, _)| other_key < key).is_some() {}
+            right.peek().is_none_or(|(other_key, _)| other_key != key)
+        })
+    }
+
+    /// Entries whose keys appear in exactly one of `self` and `other`, in
+    /// sorted order, each taken from the list that holds it.
+    ///
+    /// Both level-0 chains are merged in one pass, without any lookups.
+    ///
+    /// Time complexity: O(n + m)
+    pub fn symmetric_difference(
+        &'a self,
+        other: &'a SkipList<K, V>,
+    ) -> impl Iterator<Item = (&'a K, &'a V)> {
+        self.merge_join(other).filter_map(|side| match side {
+            MergeSide::Left(entry) | MergeSide::Right(entry) => Some(entry),
+            MergeSide::Both(..) => None,
+        })
+    }
+
+    /// Every key of `self` and `other` in sorted order, tagged with the side
+    /// it appears on: `Both` with the entries of both lists for a shared key,
+    /// `Left` or `Right` for a key of only `self` or only `other`.
+    ///
+    /// Both level-0 chains are merged in one pass, without any lookups.
+    ///
+    /// Time complexity: O(n + m)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::{MergeSide, SkipList};
+    ///
+    /// let old = SkipList::from_sorted([(1, 'a'), (2, 'b')]);
+    /// let new = SkipList::from_sorted([(2, 'c'), (3, 'd')]);
+    ///
+    /// let mut diff = old.merge_join(&new);
+    /// assert_eq!(diff.next(), Some(MergeSide::Left((&1, &'a'))));
+    /// assert_eq!(diff.next(), Some(MergeSide::Both((&2, &'b'), (&2, &'c'))));
+    /// assert_eq!(diff.next(), Some(MergeSide::Right((&3, &'d'))));
+    /// assert_eq!(diff.next(), None);
+    /// ```
+    pub fn merge_join(
+        &'a self,
+        other: &'a SkipList<K, V>,
+    ) -> impl Iterator<Item = MergeSide<'a, K, V>> {
+        let mut left = self.iter().peekable();
+        let mut right = other.iter().peekable();
+
+        std::iter::from_fn(move || match (left.peek(), right.peek()) {
+            (Some((a, _)), Some((b, _))) => match a.cmp(b) {
+                std::cmp::Ordering::Less => left.next().map(MergeSide::Left),
+                std::cmp::Ordering::Greater => right.next().map(MergeSide::Right),
+                std::cmp::Ordering::Equal => Some(MergeSide::Both(left.next()?, right.next()?)),
+            },
+            (Some(_), None) => left.next().map(MergeSide::Left),
+            (None, _) => right.next().map(MergeSide::Right),
+        })
+    }
+
+    /// Whether every key of `self` also appears in `other`.
+    ///
+    /// Time complexity: O(n + m), merging both level-0 chains
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::SkipList;
+    ///
+    /// let small = SkipList::from_sorted([(1, ()), (2, ())]);
+    /// let large = SkipList::from_sorted([(1, ()), (2, ()), (3, ())]);
+    ///
+    /// assert!(small.keys_subset_of(&large));
+    /// assert!(!large.keys_subset_of(&small));
+    /// assert!(large.keys_superset_of(&small));
+    /// ```
+    pub fn keys_subset_of(&self, other: &SkipList<K, V>) -> bool {
+        self.len <= other.len && self.difference(other).next().is_none()
+    }
+
+    /// Whether every key of `other` also appears in `self`.
+    ///
+    /// Time complexity: O(n + m), merging both level-0 chains
+    pub fn keys_superset_of(&self, other: &SkipList<K, V>) -> bool {
+        other.keys_subset_of(self)
+    }
+
+    /// Positions of all entries ordered by value, largest first. Entries with
+    /// equal values keep their positional order.
+    ///
+    /// Time complexity: O(n log n)
+    pub fn indices_sorted_by_value(&'a self) -> Vec<usize>
+    where
+        V: Ord,
+    {
+        self.entries_by_value()
+            .into_iter()
+            .map(|(position, _)| position)
+            .collect()
+    }
+
+    /// The `k` entries with the largest values, largest first. Entries with
+    /// equal values keep their positional order.
+    ///
+    /// Time complexity: O(n log n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::SkipList;
+    ///
+    /// let weights = SkipList::from_sorted([("a", 3), ("b", 9), ("c", 1), ("d", 7)]);
+    ///
+    /// let top: Vec<_> = weights.top_k_by_value(2).into_iter().map(|(&k, _)| k).collect();
+    /// assert_eq!(top, vec!["b", "d"]);
+    /// ```
+    pub fn top_k_by_value(&'a self, k: usize) -> Vec<(&'a K, &'a V)>
+    where
+        V: Ord,
+    {
+        let mut entries = self.entries_by_value();
+        entries.truncate(k);
+        entries.into_iter().map(|(_, entry)| entry).collect()
+    }
+
+    /// `(position, entry)` pairs stably sorted by descending value.
+    fn entries_by_value(&'a self) -> Vec<(usize, (&'a K, &'a V))>
+    where
+        V: Ord,
+    {
+        let mut entries: Vec<_> = self.iter().enumerate().collect();
+        entries.sort_by(|(_, (_, a)), (_, (_, b))| b.cmp(a));
+        entries
+    }
+
+    /// Iterate over `(&key, &value, span)` in sorted order, where `span` is
+    /// the node's outgoing span on level 0.
+    ///
+    /// Every level-0 span must be 1; this exposes them so that invariant can
+    /// be asserted directly.
+    #[cfg(feature = "test-utils")]
+    pub fn iter_with_spans(&'a self) -> impl Iterator<Item = (&'a K, &'a V, usize)> {
+        let mut current = unsafe { self.head.as_ref() }.forward[0].ptr;
+        std::iter::from_fn(move || {
+            if self.is_tail(current) {
+                return None;
+            }
+            let node = unsafe { current.as_ref() };
+            current = node.forward[0].ptr;
+            Some((node.key(), node.value(), node.forward[0].span))
+        })
+    }
+}
+
+/// Iterator over the entries whose keys fall in a range, created by
+/// `SkipList::range`.
+pub struct SkipListRange<'a, K: Key, V: Value> {
+    ptr: NodePtr<K, V>,
+    remaining: usize,
+    _marker: std::marker::PhantomData<&'a SkipList<K, V>>,
+}
+
+impl<'a, K: Key, V: Value> Iterator for SkipListRange<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        let node = unsafe { self.ptr.as_ref() };
+        self.ptr = node.forward[0].ptr;
+        self.remaining -= 1;
+
+        Some((node.key(), node.value()))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<K: Key, V: Value> ExactSizeIterator for SkipListRange<'_, K, V> {}
+
+impl<K: Key, V: Value> SkipList<K, V> {
+    /// Iterate over the entries whose keys fall in `range`, in sorted order.
+    ///
+    /// Both bounds are located up front with one descent each, so the number
+    /// of entries is known exactly. Empty or reversed ranges yield nothing.
+    ///
+    /// Time complexity: O(log n) expected to seek, then O(1) per entry
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::SkipList;
+    ///
+    /// let skip_list = SkipList::from_sorted((0..10).map(|i| (i, i * 10)));
+    ///
+    /// let entries: Vec<_> = skip_list.range(3..6).collect();
+    /// assert_eq!(entries, vec![(&3, &30), (&4, &40), (&5, &50)]);
+    /// ```
+    pub fn range<Q, R>(&self, range: R) -> SkipListRange<'_, K, V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        let ((start, start_pos), (_, end_pos)) = self.range_nodes(range);
+
+        SkipListRange {
+            ptr: start,
+            remaining: end_pos - start_pos,
+            _marker: std::marker::PhantomData,
+        }
+    }
+
+    /// Iterate over at most `n` entries whose keys fall in `range`, starting
+    /// from its lower bound.
+    ///
+    /// Equivalent to `range(range).take(n)`, but the iterator reports the
+    /// exact number of entries it yields, `min(n, entries in range)`.
+    ///
+    /// Time complexity: O(log n) expected to seek, then O(1) per entry
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::SkipList;
+    ///
+    /// let skip_list = SkipList::from_sorted((0..100).map(|i| (i, i)));
+    ///
+    /// let keys: Vec<_> = skip_list.range_take(40.., 3).map(|(&k, _)| k).collect();
+    /// assert_eq!(keys, vec![40, 41, 42]);
+    /// ```
+    pub fn range_take<Q, R>(&self, range: R, n: usize) -> SkipListRange<'_, K, V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        let mut iter = self.range(range);
+        iter.remaining = iter.remaining.min(n);
+        iter
+    }
+
+    /// Seek to the first key not less than `from`, then iterate while `pred`
+    /// holds, stopping at the first entry for which it does not.
+    ///
+    /// Time complexity: O(log n) expected to seek, then O(1) per entry
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::SkipList;
+    ///
+    /// let skip_list = SkipList::from_sorted((0..10).map(|i| (i, i * 20)));
+    ///
+    /// let keys: Vec<_> = skip_list
+    ///     .range_take_while(&5, |_, &v| v < 160)
+    ///     .map(|(&k, _)| k)
+    ///     .collect();
+    /// assert_eq!(keys, vec![5, 6, 7]);
+    /// ```
+    pub fn range_take_while<Q, F>(&self, from: &Q, mut pred: F) -> impl Iterator<Item = (&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        F: FnMut(&K, &V) -> bool,
+    {
+        self.range((Bound::Included(from), Bound::Unbounded))
+            .take_while(move |(key, value)| pred(key, value))
+    }
+
+    /// Seek to the start of `range`, then return the first entry in it for
+    /// which `pred` holds, or `None` if there is none.
+    ///
+    /// Time complexity: O(log n + k) expected, where k is the number of
+    /// entries tested
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::SkipList;
+    ///
+    /// let skip_list = SkipList::from_sorted((0..10).map(|i| (i, i * i)));
+    ///
+    /// assert_eq!(skip_list.range_find(3.., |_, &v| v > 20), Some((&5, &25)));
+    /// assert_eq!(skip_list.range_find(..5, |_, &v| v > 20), None);
+    /// ```
+    pub fn range_find<Q, R, F>(&self, range: R, mut pred: F) -> Option<(&K, &V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+        F: FnMut(&K, &V) -> bool,
+    {
+        self.range(range).find(|(key, value)| pred(key, value))
+    }
+
+    /// Seek to the start of `range`, then fold `f` over the entries in it,
+    /// threading `state` through each one in sorted order.
+    ///
+    /// Time complexity: O(log n + k) expected, where k is the number of
+    /// entries in the range
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::SkipList;
+    ///
+    /// let skip_list = SkipList::from_sorted((0..10).map(|i| (i, i * 10)));
+    ///
+    /// let sum = skip_list.range_scan(2..5, 0, |acc, _, &v| acc + v);
+    /// assert_eq!(sum, 20 + 30 + 40);
+    /// ```
+    pub fn range_scan<Q, R, S, F>(&self, range: R, state: S, mut f: F) -> S
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+        F: FnMut(S, &K, &V) -> S,
+    {
+        self.range(range)
+            .fold(state, |state, (key, value)| f(state, key, value))
+    }
+
+    /// Keys within `range`, in sorted order.
+    pub fn keys_in_range<Q, R>(&self, range: R) -> impl Iterator<Item = &K>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        self.range(range).map(|(key, _)| key)
+    }
+
+    /// Values whose keys are within `range`, in key order.
+    pub fn values_in_range<Q, R>(&self, range: R) -> impl Iterator<Item = &V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        self.range(range).map(|(_, value)| value)
+    }
+
+    /// Iterate over the entries whose 0-based positions fall in `range`.
+    ///
+    /// Positions past the end are clamped to the list's length, so `..=len`
+    /// yields the same entries as `..=len - 1`. An empty or reversed range
+    /// yields nothing.
+    ///
+    /// Time complexity: O(log n + k) expected, where k is the number of
+    /// entries yielded
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::SkipList;
+    ///
+    /// let skip_list = SkipList::from_sorted((0..10).map(|i| (i, i * 10)));
+    ///
+    /// let keys: Vec<_> = skip_list.position_range(2..=4).map(|(&k, _)| k).collect();
+    /// assert_eq!(keys, vec![2, 3, 4]);
+    /// ```
+    pub fn position_range<R: RangeBounds<usize>>(&self, range: R) -> SkipListRange<'_, K, V> {
+        let (start, end) = self.position_bounds(range);
+
+        if start >= end {
+            return SkipListRange {
+                ptr: self.tail,
+                remaining: 0,
+                _marker: std::marker::PhantomData,
+            };
+        }
+
+        SkipListRange {
+            // `start < end <= len`, so the node exists
+            ptr: self.node_at(start).unwrap(),
+            remaining: end - start,
+            _marker: std::marker::PhantomData,
+        }
+    }
+
+    /// Iterate over the entries whose 0-based positions fall in `range`, with
+    /// mutable references to their values, e.g. to update the rows of a
+    /// view. Positions are clamped like in `position_range`.
+    ///
+    /// Time complexity: O(log n + k) expected, where k is the number of
+    /// entries yielded
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::SkipList;
+    ///
+    /// let mut skip_list = SkipList::from_sorted((0..5).map(|i| (i, i)));
+    /// for (_, value) in skip_list.position_range_mut(1..3) {
+    ///     *value *= 10;
+    /// }
+    ///
+    /// let values: Vec<_> = skip_list.iter().map(|(_, &v)| v).collect();
+    /// assert_eq!(values, vec![0, 10, 20, 3, 4]);
+    /// ```
+    pub fn position_range_mut<R: RangeBounds<usize>>(
+        &mut self,
+        range: R,
+    ) -> impl Iterator<Item = (&K, &mut V)> {
+        let (start, end) = self.position_bounds(range);
+        let mut current = if start < end {
+            self.node_at(start).unwrap()
+        } else {
+            self.tail
+        };
+
+        // The pointer moves on before an entry is handed out, and each node
+        // is visited once, so the references never alias
+        (start..end).map(move |_| {
+            let mut node = current;
+            current = unsafe { node.as_ref() }.forward[0].ptr;
+            let node = unsafe { node.as_mut() };
+            unsafe { (node.key.assume_init_ref(), node.value.assume_init_mut()) }
+        })
+    }
+
+    /// Collect `position_range_mut` into a `Vec`, for random access into a
+    /// page of entries, e.g. to edit rows of a paginated view in any order.
+    ///
+    /// Time complexity: O(log n + k) expected, where k is the number of
+    /// entries returned
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::SkipList;
+    ///
+    /// let mut skip_list = SkipList::from_sorted((0..10).map(|i| (i, i)));
+    /// let mut page = skip_list.index_entries_mut(4..7);
+    /// *page[2].1 = 60;
+    /// *page[0].1 = 40;
+    ///
+    /// assert_eq!(skip_list.index(4), Some((&4, &40)));
+    /// assert_eq!(skip_list.index(6), Some((&6, &60)));
+    /// ```
+    pub fn index_entries_mut<R: RangeBounds<usize>>(&mut self, range: R) -> Vec<(&K, &mut V)> {
+        self.position_range_mut(range).collect()
+    }
+
+    /// Resolve a range of positions to `start..end` with `end` clamped to
+    /// the length. `start` may exceed `end` for an empty or reversed range.
+    fn position_bounds<R: RangeBounds<usize>>(&self, range: R) -> (usize, usize) {
+        let start = match range.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start.saturating_add(1),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&end) => end.saturating_add(1),
+            Bound::Excluded(&end) => end,
+            Bound::Unbounded => self.len,
+        }
+        .min(self.len);
+
+        (start, end)
+    }
+
+    /// Iterate from position `start` to the end, e.g. to resume paging where
+    /// an earlier page stopped. Empty if `start >= len`.
+    ///
+    /// Time complexity: O(log n) expected to seek, then O(1) per entry
+    pub fn iter_from_index(&self, start: usize) -> SkipListRange<'_, K, V> {
+        self.position_range(start..)
+    }
 }
 
 // pub struct SkipListIterMut<'a, K: Key, V: Value> {
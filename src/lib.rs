@@ -1,6 +1,35 @@
-use std::{borrow::Borrow, fmt, mem::MaybeUninit, ptr::NonNull};
-
+use std::{
+    borrow::Borrow,
+    collections::{BTreeMap, TryReserveError},
+    fmt,
+    mem::MaybeUninit,
+    ops::{Add, Bound, Range, RangeBounds, Sub},
+    ptr::NonNull,
+};
+
+use rand::{Rng, RngCore};
+
+mod builder;
+mod bytes;
+mod cursor;
+mod entry;
 mod iter;
+#[cfg(feature = "rayon")]
+mod par;
+mod persistent;
+mod pool;
+mod retain;
+mod stats;
+mod text;
+
+pub use builder::{BuildError, SkipListBuilder};
+pub use bytes::{DecodeError, FromBytes, ToBytes};
+pub use cursor::{Cursor, InsertCursor};
+pub use entry::{Entry, OccupiedEntry, VacantEntry};
+pub use iter::MergeSide;
+pub use persistent::{PersistentIter, PersistentSkipList};
+pub use pool::NodePool;
+pub use stats::SkipListStats;
 
 pub trait Key: Ord {}
 
@@ -42,6 +71,9 @@ impl<K: Key + fmt::Display, V: Value + fmt::Display> fmt::Display for Node<K, V>
 
 type NodePtr<K, V> = NonNull<Node<K, V>>;
 
+/// A node together with its 0-based position (the tail's position is `len`).
+type PositionedNode<K, V> = (NodePtr<K, V>, usize);
+
 #[derive(Debug)]
 struct ForwardPtr<K, V> {
     ptr: NodePtr<K, V>,
@@ -50,10 +82,7 @@ struct ForwardPtr<K, V> {
 
 impl<K, V> Clone for ForwardPtr<K, V> {
     fn clone(&self) -> Self {
-        Self {
-            ptr: self.ptr,
-            span: self.span,
-        }
+        *self
     }
 }
 
@@ -74,15 +103,153 @@ impl<K, V> Default for ForwardPtr<K, V> {
     }
 }
 
+/// Spans of the two links that replace a link of `span` starting at position
+/// `from` when a node is inserted at position `at` under it. The old target
+/// moves one position up, so together they cover `span + 1`.
+fn split_span(from: usize, span: usize, at: usize) -> (usize, usize) {
+    debug_assert!(
+        from < at && at <= from + span,
+        "inserting at position {} outside the link from {} spanning {}",
+        at,
+        from,
+        span
+    );
+    (at - from, from + span - at + 1)
+}
+
+/// Span of the link that replaces a link of `left` followed by one of
+/// `right` when the node between them is removed.
+fn join_spans(left: usize, right: usize) -> usize {
+    debug_assert!(
+        left >= 1 && right >= 1,
+        "joining spans {} and {}, spans are at least 1",
+        left,
+        right
+    );
+    left + (right - 1)
+}
+
+/// Span of a link passing over a removed node.
+fn shrink_span(span: usize) -> usize {
+    debug_assert!(
+        span >= 2,
+        "a link of span {} cannot pass over a removed node",
+        span
+    );
+    span - 1
+}
+
+/// Draws tower levels: each extra level is kept with `probability`, up to
+/// `max_level`, or up to a cap that grows with the length when `dynamic` is
+/// set. Falls back to the thread-local RNG when none was supplied.
+struct LevelGenerator {
+    probability: f64,
+    max_level: usize,
+    dynamic: bool,
+    rng: Option<Box<dyn RngCore>>,
+}
+
+impl LevelGenerator {
+    /// Draw the level of a node joining a list that holds `len` entries.
+    fn random_level(&mut self, len: usize) -> usize {
+        let (probability, max_level) = (self.probability, self.cap(len));
+
+        match &mut self.rng {
+            Some(rng) => draw_level(rng.as_mut(), probability, max_level),
+            None => draw_level(&mut rand::rng(), probability, max_level),
+        }
+    }
+
+    /// Same settings, drawing from the thread-local RNG.
+    fn unseeded(&self) -> Self {
+        Self {
+            probability: self.probability,
+            max_level: self.max_level,
+            dynamic: self.dynamic,
+            rng: None,
+        }
+    }
+
+    /// Highest level a node may get in a list of `len` entries: `max_level`,
+    /// or in dynamic mode the expected height of such a list.
+    fn cap(&self, len: usize) -> usize {
+        if self.dynamic {
+            self.expected_height(len)
+        } else {
+            self.max_level
+        }
+    }
+
+    /// Expected top level of a list of `len` entries, `ceil(log_{1/p}(len + 1))`.
+    fn expected_height(&self, len: usize) -> usize {
+        ((len as f64 + 1.0).log(1.0 / self.probability)).ceil() as usize
+    }
+}
+
+/// Geometric level: keep climbing while a coin with `probability` comes up.
+///
+/// For the default probability of 0.5 the coin flips are the bits of a single
+/// `u64`: the number of trailing zeros is at least `k` with probability
+/// `0.5^k`, exactly like `k` heads in a row.
+fn draw_level<R: RngCore + ?Sized>(rng: &mut R, probability: f64, max_level: usize) -> usize {
+    if probability == 0.5 && max_level <= u64::BITS as usize {
+        return (rng.next_u64().trailing_zeros() as usize).min(max_level);
+    }
+
+    coin_flip_level(rng, probability, max_level)
+}
+
+/// One RNG draw per coin flip, for any probability.
+fn coin_flip_level<R: RngCore + ?Sized>(rng: &mut R, probability: f64, max_level: usize) -> usize {
+    let mut level = 0;
+
+    while rng.random::<f64>() < probability && level < max_level {
+        level += 1;
+    }
+
+    level
+}
+
+impl Default for LevelGenerator {
+    fn default() -> Self {
+        Self {
+            probability: DEFAULT_PROBABILITY,
+            max_level: MAX_LEVEL,
+            dynamic: false,
+            rng: None,
+        }
+    }
+}
+
+impl fmt::Debug for LevelGenerator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LevelGenerator")
+            .field("probability", &self.probability)
+            .field("max_level", &self.max_level)
+            .field("dynamic", &self.dynamic)
+            .field("seeded", &self.rng.is_some())
+            .finish()
+    }
+}
+
 #[derive(Debug)]
 pub struct SkipList<K: Key, V: Value> {
     head: NodePtr<K, V>,
     tail: NodePtr<K, V>,
     level: usize,
     len: usize,
+    levels: LevelGenerator,
+    pool: Option<NodePool<K, V>>,
+    auto_shrink: bool,
 }
 
 const MAX_LEVEL: usize = 32;
+const DEFAULT_PROBABILITY: f64 = 0.5;
+
+/// Longest list `format_tower` draws in full.
+const TOWER_MAX_NODES: usize = 64;
+/// Columns `format_tower` keeps at each end of a longer list.
+const TOWER_EDGE_NODES: usize = 8;
 
 impl<K: Key, V: Value> SkipList<K, V> {
     pub fn new() -> Self {
@@ -112,7 +279,264 @@ impl<K: Key, V: Value> SkipList<K, V> {
             tail: tail_ptr,
             level: 0,
             len: 0,
+            levels: LevelGenerator::default(),
+            pool: None,
+            auto_shrink: false,
+        }
+    }
+
+    /// Build a skip list from key-value pairs that are already sorted by key.
+    ///
+    /// The keys must be strictly increasing. Unlike repeated `insert`, this
+    /// appends every node at the end of each level without searching, so the
+    /// whole list is built in a single linear pass.
+    ///
+    /// Time complexity: O(n) expected
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::SkipList;
+    ///
+    /// let skip_list = SkipList::from_sorted(vec![(1, "a"), (2, "b"), (3, "c")]);
+    ///
+    /// assert_eq!(skip_list.len(), 3);
+    /// assert_eq!(skip_list.index(1), Some((&2, &"b")));
+    /// ```
+    pub fn from_sorted<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        Self::from_sorted_with_generator(iter, LevelGenerator::default())
+    }
+
+    /// Like `from_sorted`, drawing node levels from `levels`, which the list
+    /// then keeps for later inserts.
+    fn from_sorted_with_generator<I: IntoIterator<Item = (K, V)>>(
+        iter: I,
+        mut levels: LevelGenerator,
+    ) -> Self {
+        let mut list = Self::from_sorted_with_levels(
+            iter.into_iter()
+                .enumerate()
+                .map(|(len, (key, value))| (key, value, levels.random_level(len))),
+        );
+        list.levels = levels;
+        list
+    }
+
+    /// Give `self` the configuration of `other`: its level settings (but not
+    /// a custom RNG, which cannot be shared), its node pool and auto-shrink.
+    fn inherit_config(&mut self, other: &Self) {
+        self.levels = other.levels.unseeded();
+        self.pool = other.pool.clone();
+        self.auto_shrink = other.auto_shrink;
+    }
+
+    /// Split the list into `n` lists holding contiguous runs of its entries,
+    /// in order. The first `len % n` chunks get one entry more than the
+    /// rest, so sizes differ by at most one; chunks are empty if `n > len`.
+    ///
+    /// Every chunk keeps the level settings, node pool and auto-shrink
+    /// setting of the original (but not a custom RNG).
+    ///
+    /// Time complexity: O(n + len) expected
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::SkipList;
+    ///
+    /// let skip_list = SkipList::from_sorted((0..10).map(|i| (i, i)));
+    /// let chunks = skip_list.split_into_chunks(3);
+    ///
+    /// let sizes: Vec<_> = chunks.iter().map(|chunk| chunk.len()).collect();
+    /// assert_eq!(sizes, vec![4, 3, 3]);
+    /// assert_eq!(chunks[1].index(0), Some((&4, &4)));
+    /// ```
+    pub fn split_into_chunks(self, n: usize) -> Vec<Self> {
+        assert!(n > 0, "cannot split into 0 chunks");
+
+        let (size, extra) = (self.len / n, self.len % n);
+        let mut template = Self::new();
+        template.inherit_config(&self);
+
+        let mut entries = self.into_iter();
+        (0..n)
+            .map(|i| {
+                let chunk_len = size + (i < extra) as usize;
+                let mut chunk = Self::from_sorted_with_generator(
+                    entries.by_ref().take(chunk_len),
+                    template.levels.unseeded(),
+                );
+                chunk.inherit_config(&template);
+                chunk
+            })
+            .collect()
+    }
+
+    /// Consume the list, splitting it into the entries for which `f` returns
+    /// `true` and those for which it returns `false`, like
+    /// `Iterator::partition` but producing two lists.
+    ///
+    /// Entries are taken in sorted order, so both halves are linked in
+    /// linearly as with `from_sorted`. Both keep the level settings, node pool
+    /// and auto-shrink setting of the original (but not a custom RNG).
+    ///
+    /// Time complexity: O(n) expected
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::SkipList;
+    ///
+    /// let skip_list = SkipList::from_sorted((0..6).map(|i| (i, i)));
+    /// let (even, odd) = skip_list.partition(|k, _| k % 2 == 0);
+    ///
+    /// assert_eq!(even.iter().map(|(k, _)| *k).collect::<Vec<_>>(), vec![0, 2, 4]);
+    /// assert_eq!(odd.iter().map(|(k, _)| *k).collect::<Vec<_>>(), vec![1, 3, 5]);
+    /// ```
+    pub fn partition<F: FnMut(&K, &V) -> bool>(self, mut f: F) -> (Self, Self) {
+        let mut template = Self::new();
+        template.inherit_config(&self);
+
+        let (matching, rest): (Vec<_>, Vec<_>) =
+            self.into_iter().partition(|(key, value)| f(key, value));
+        let build = |entries: Vec<(K, V)>| {
+            let mut list = Self::from_sorted_with_generator(entries, template.levels.unseeded());
+            list.inherit_config(&template);
+            list
+        };
+        (build(matching), build(rest))
+    }
+
+    /// Like `from_sorted`, but the keys only need to be non-decreasing: runs of
+    /// equal adjacent keys collapse into a single node holding the last value
+    /// of the run, matching what repeated `insert` would leave behind.
+    ///
+    /// Time complexity: O(n) expected
+    pub fn from_sorted_dedup<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut iter = iter.into_iter().peekable();
+        let deduped = std::iter::from_fn(move || {
+            let (key, mut value) = iter.next()?;
+            while let Some((_, next_value)) = iter.next_if(|(next_key, _)| next_key == &key) {
+                value = next_value;
+            }
+            Some((key, value))
+        });
+
+        Self::from_sorted(deduped)
+    }
+
+    /// Build a new list holding every key of `self` and `other`, leaving both
+    /// untouched. For a key present in both lists the value is
+    /// `combine(self_value, other_value)`.
+    ///
+    /// Both level-0 chains are merged in a single pass and the result is
+    /// built with `from_sorted`.
+    ///
+    /// Time complexity: O(n + m) expected
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::SkipList;
+    ///
+    /// let a = SkipList::from_sorted([(1, 1), (3, 3)]);
+    /// let b = SkipList::from_sorted([(2, 20), (3, 30)]);
+    ///
+    /// let union = a.union(&b, |x, y| x + y);
+    /// let entries: Vec<_> = union.iter().map(|(&k, &v)| (k, v)).collect();
+    /// assert_eq!(entries, vec![(1, 1), (2, 20), (3, 33)]);
+    /// ```
+    pub fn union<F>(&self, other: &Self, combine: F) -> Self
+    where
+        K: Clone,
+        V: Clone,
+        F: Fn(&V, &V) -> V,
+    {
+        let mut left = self.iter().peekable();
+        let mut right = other.iter().peekable();
+
+        let merged = std::iter::from_fn(|| {
+            let (key, value) = match (left.peek(), right.peek()) {
+                (Some((a, _)), Some((b, _))) => match a.cmp(b) {
+                    std::cmp::Ordering::Less => left.next()?,
+                    std::cmp::Ordering::Greater => right.next()?,
+                    std::cmp::Ordering::Equal => {
+                        let (key, a) = left.next()?;
+                        let (_, b) = right.next()?;
+                        return Some((key.clone(), combine(a, b)));
+                    }
+                },
+                (Some(_), None) => left.next()?,
+                (None, Some(_)) => right.next()?,
+                (None, None) => return None,
+            };
+            Some((key.clone(), value.clone()))
+        });
+
+        Self::from_sorted(merged)
+    }
+
+    /// Linear builder shared by the sorted constructors: every entry carries
+    /// the level its node should have.
+    fn from_sorted_with_levels<I: IntoIterator<Item = (K, V, usize)>>(iter: I) -> Self {
+        let mut list = Self::new();
+
+        // Last node seen on each level and its position (head is position 0)
+        let mut last = vec![list.head];
+        let mut last_pos = vec![0];
+        let mut pos = 0;
+
+        for (key, value, level) in iter {
+            pos += 1;
+
+            while list.level < level {
+                unsafe { list.head.as_mut() }.forward.push(ForwardPtr {
+                    ptr: list.tail,
+                    span: 0,
+                });
+                last.push(list.head);
+                last_pos.push(0);
+                list.level += 1;
+            }
+
+            let new_node_ptr = list.alloc_node(key, value, level);
+
+            for i in 0..=level {
+                unsafe { last[i].as_mut() }.forward[i] = ForwardPtr {
+                    ptr: new_node_ptr,
+                    span: pos - last_pos[i],
+                };
+                last[i] = new_node_ptr;
+                last_pos[i] = pos;
+            }
+        }
+
+        for i in 0..=list.level {
+            unsafe { last[i].as_mut() }.forward[i] = ForwardPtr {
+                ptr: list.tail,
+                span: pos + 1 - last_pos[i],
+            };
         }
+
+        list.len = pos;
+        list
+    }
+
+    /// Take a point-in-time copy of the list, e.g. to iterate over it while
+    /// the original keeps being mutated.
+    ///
+    /// This is currently a full clone of the exact structure, O(n) in time and
+    /// memory; it is a separate method so that it can later share structure.
+    pub fn snapshot(&self) -> Self
+    where
+        K: Clone,
+        V: Clone,
+    {
+        self.clone()
     }
 
     pub fn len(&self) -> usize {
@@ -171,272 +595,2112 @@ impl<K: Key, V: Value> SkipList<K, V> {
         true
     }
 
-    fn is_head(&self, node: NodePtr<K, V>) -> bool {
-        node == self.head
+    /// Whether the keys along level 0 are strictly increasing and there are
+    /// exactly `len` of them. Much cheaper than `verify_spans`, with no
+    /// allocation, so a fuzz harness can run it after every operation.
+    ///
+    /// Time complexity: O(n)
+    #[cfg(any(test, feature = "test-utils"))]
+    pub fn verify_order(&self) -> bool {
+        let mut count = 0;
+        let mut previous: Option<&K> = None;
+        let mut current = unsafe { self.head.as_ref() }.forward[0].ptr;
+
+        while !self.is_tail(current) {
+            let node = unsafe { current.as_ref() };
+            if previous.is_some_and(|previous| previous >= node.key()) {
+                return false;
+            }
+            previous = Some(node.key());
+            count += 1;
+            current = node.forward[0].ptr;
+        }
+
+        count == self.len
     }
 
-    fn is_tail(&self, node: NodePtr<K, V>) -> bool {
-        node == self.tail
+    /// Span of every forward pointer, level by level: `spans[i]` lists the
+    /// spans met while following level `i` from the head to the tail.
+    #[cfg(any(test, feature = "test-utils"))]
+    pub fn dump_spans(&self) -> Vec<Vec<usize>> {
+        (0..=self.level)
+            .map(|level| {
+                let mut spans = vec![];
+                let mut current = self.head;
+                while !self.is_tail(current) {
+                    let forward_ptr = unsafe { current.as_ref() }.forward[level];
+                    spans.push(forward_ptr.span);
+                    current = forward_ptr.ptr;
+                }
+                spans
+            })
+            .collect()
     }
 
-    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
-        let level = Self::rand_level();
+    /// Sum of the spans met while following `level` from the head to the
+    /// tail. Every level reaches the tail at position `len + 1`, so this is
+    /// `len + 1` on every level of a consistent list: a quick numeric check,
+    /// one level at a time, next to the full `verify_spans`.
+    ///
+    /// Time complexity: O(nodes on `level`)
+    ///
+    /// # Panics
+    ///
+    /// Panics if `level` is above the list's top level.
+    #[cfg(feature = "test-utils")]
+    pub fn total_span_at_level(&self, level: usize) -> usize {
+        assert!(
+            level <= self.level,
+            "level {} is above the top level {}",
+            level,
+            self.level
+        );
 
-        if level > self.level {
-            for _ in (self.level + 1)..=level {
-                unsafe {
-                    self.head.as_mut().forward.push(ForwardPtr {
-                        ptr: self.tail,
-                        span: self.len + 1,
-                    });
-                }
+        let mut total = 0;
+        let mut current = self.head;
+        while !self.is_tail(current) {
+            let forward_ptr = unsafe { current.as_ref() }.forward[level];
+            total += forward_ptr.span;
+            current = forward_ptr.ptr;
+        }
+        total
+    }
+
+    /// Whether both lists hold the same entries in nodes of the same levels,
+    /// linked with the same spans on every level. Stricter than comparing
+    /// contents: two lists built by different insert orders iterate the same
+    /// but usually differ in structure.
+    #[cfg(feature = "test-utils")]
+    pub fn structure_eq(&self, other: &Self) -> bool
+    where
+        V: PartialEq,
+    {
+        if self.len != other.len || self.level != other.level {
+            return false;
+        }
+
+        let mut a = unsafe { self.head.as_ref() }.forward[0].ptr;
+        let mut b = unsafe { other.head.as_ref() }.forward[0].ptr;
+        while !self.is_tail(a) {
+            let (node_a, node_b) = unsafe { (a.as_ref(), b.as_ref()) };
+            if node_a.level != node_b.level
+                || node_a.key() != node_b.key()
+                || node_a.value() != node_b.value()
+            {
+                return false;
             }
-            self.level = level;
+            a = node_a.forward[0].ptr;
+            b = node_b.forward[0].ptr;
         }
 
-        let mut update = vec![NodePtr::dangling(); self.level + 1];
-        let mut steps = vec![0; self.level + 1];
-        let mut step = 0;
+        self.dump_spans() == other.dump_spans()
+    }
 
-        let mut cur = self.head;
-        for i in (0..=self.level).rev() {
-            loop {
-                let cur_node_ref = unsafe { cur.as_ref() };
-                let next = cur_node_ref.forward[i].ptr;
+    /// Check every structural invariant and panic with a message naming the
+    /// first one that is violated: the level, the node (by index, or the
+    /// head) and the expected against the actual value.
+    ///
+    /// Checked are the length, strictly increasing keys, tower shapes, that
+    /// every level visits exactly the nodes tall enough for it, span
+    /// correctness, and that the top level is not empty. With the `paranoid`
+    /// feature this runs after every change to the structure: each node
+    /// linked or unlinked, each run detached and each `retain`-style pass.
+    ///
+    /// Time complexity: O(n log n) expected
+    pub fn assert_invariants(&self) {
+        let describe = |position: usize| match position {
+            0 => "the head".to_string(),
+            _ => format!("the node at index {}", position - 1),
+        };
+
+        let head = unsafe { self.head.as_ref() };
+        assert_eq!(
+            head.forward.len(),
+            self.level + 1,
+            "skip list invariant violated: the head has {} levels, expected {}",
+            head.forward.len(),
+            self.level + 1
+        );
+
+        // Level 0 fixes every node's position; count the nodes reaching each level
+        let mut positions = std::collections::HashMap::from([(self.head, 0)]);
+        let mut tall_enough = vec![0; self.level + 1];
+        let mut previous: Option<&K> = None;
+        let mut cur = head.forward[0].ptr;
+        let mut position = 0;
+        while !self.is_tail(cur) {
+            position += 1;
+            assert!(
+                position <= self.len,
+                "skip list invariant violated: level 0 has more nodes than len = {}",
+                self.len
+            );
 
-                if self.is_tail(next) {
-                    break;
-                }
-                let next_key = (unsafe { next.as_ref() }).key();
-                if next_key < &key {
-                    step += cur_node_ref.forward[i].span;
-                    cur = next;
-                } else {
-                    break;
+            let node = unsafe { cur.as_ref() };
+            assert!(
+                node.forward.len() == node.level + 1 && node.level <= self.level,
+                "skip list invariant violated: {} has level {} and {} forward pointers, \
+                 in a list of level {}",
+                describe(position),
+                node.level,
+                node.forward.len(),
+                self.level
+            );
+            assert!(
+                previous.is_none_or(|previous| previous < node.key()),
+                "skip list invariant violated: the key of {} is not greater than the one before it",
+                describe(position)
+            );
+
+            previous = Some(node.key());
+            for count in &mut tall_enough[..=node.level] {
+                *count += 1;
+            }
+            positions.insert(cur, position);
+            cur = node.forward[0].ptr;
+        }
+        assert_eq!(
+            position, self.len,
+            "skip list invariant violated: level 0 has {} nodes, expected len = {}",
+            position, self.len
+        );
+        positions.insert(self.tail, self.len + 1);
+
+        for (level, &expected) in tall_enough.iter().enumerate() {
+            let mut cur = self.head;
+            let mut visited = 0;
+            while !self.is_tail(cur) {
+                let position = positions[&cur];
+                let forward_ptr = unsafe { cur.as_ref() }.forward[level];
+                let Some(&next_position) = positions.get(&forward_ptr.ptr) else {
+                    panic!(
+                        "skip list invariant violated: level {}, {} links to a node missing from level 0",
+                        level,
+                        describe(position)
+                    );
+                };
+                assert!(
+                    next_position > position,
+                    "skip list invariant violated: level {}, {} links backwards to {}",
+                    level,
+                    describe(position),
+                    describe(next_position)
+                );
+                assert_eq!(
+                    forward_ptr.span,
+                    next_position - position,
+                    "skip list invariant violated: level {}, {}: span is {}, expected {}",
+                    level,
+                    describe(position),
+                    forward_ptr.span,
+                    next_position - position
+                );
+
+                cur = forward_ptr.ptr;
+                if !self.is_tail(cur) {
+                    visited += 1;
                 }
             }
-            update[i] = cur;
-            steps[i] = step;
+
+            assert_eq!(
+                visited, expected,
+                "skip list invariant violated: level {} links {} nodes, expected the {} of level {} or more",
+                level, visited, expected, level
+            );
         }
 
-        let mut next = unsafe { cur.as_ref() }.forward[0].ptr;
+        assert!(
+            self.level == 0 || tall_enough[self.level] > 0,
+            "skip list invariant violated: the top level {} is empty",
+            self.level
+        );
+    }
 
-        if !self.is_tail(next) && unsafe { next.as_ref() }.key() == &key {
-            // already exists, replace value
-            let old_v = std::mem::replace(unsafe { next.as_mut() }.value_mut(), value);
+    /// `assert_invariants` in builds with debug assertions, nothing otherwise.
+    pub fn debug_assert_invariants(&self) {
+        if cfg!(debug_assertions) {
+            self.assert_invariants();
+        }
+    }
 
-            return Some(old_v);
+    /// Build a list directly from its internal layout: every node with its
+    /// level, and the per-level span lists in the format of `dump_spans`.
+    /// No levels are drawn and no searching is done.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the shape of `spans` does not match the node levels: there
+    /// must be one list per level up to the highest node level (at least one
+    /// list), and `spans[i]` must hold one span per node of level `i` or
+    /// more, plus one for the head.
+    ///
+    /// # Safety
+    ///
+    /// The keys must be strictly increasing and every span must equal the
+    /// distance between the nodes it connects, as `verify_spans` checks.
+    /// Positional and ordered lookups on a list violating this return
+    /// arbitrary results.
+    #[cfg(feature = "test-utils")]
+    pub unsafe fn from_raw_parts(nodes: Vec<(K, V, usize)>, spans: Vec<Vec<usize>>) -> Self {
+        let top = nodes.iter().map(|&(_, _, level)| level).max().unwrap_or(0);
+        assert_eq!(spans.len(), top + 1, "expected one span list per level");
+
+        let list = Self::from_sorted_with_levels(nodes);
+        for (level, level_spans) in spans.iter().enumerate() {
+            let mut current = list.head;
+            let mut count = 0;
+            while !list.is_tail(current) {
+                assert!(
+                    count < level_spans.len(),
+                    "too few spans on level {}",
+                    level
+                );
+                let forward_ptr = &mut unsafe { current.as_mut() }.forward[level];
+                forward_ptr.span = level_spans[count];
+                current = forward_ptr.ptr;
+                count += 1;
+            }
+            assert_eq!(
+                count,
+                level_spans.len(),
+                "too many spans on level {}",
+                level
+            );
         }
 
-        // cur = next;
+        list
+    }
 
-        step += 1;
+    /// Check the span invariant and, if it is broken, rebuild the whole tower
+    /// from the level 0 chain. Returns `true` if a repair was performed.
+    ///
+    /// Only level 0 is trusted: its entries are drained in order and inserted
+    /// again, so every upper level and span is recomputed from scratch.
+    /// Everything else about the list is kept.
+    ///
+    /// Time complexity: O(n log n) expected
+    #[cfg(feature = "test-utils")]
+    pub fn validate_and_repair(&mut self) -> bool {
+        if self.verify_spans() {
+            return false;
+        }
 
-        let mut forward = vec![ForwardPtr::default(); level + 1];
+        // Only the nodes move out, to a scratch list
+        let mut broken = Self::new();
+        std::mem::swap(&mut self.head, &mut broken.head);
+        std::mem::swap(&mut self.tail, &mut broken.tail);
+        std::mem::swap(&mut self.level, &mut broken.level);
+        std::mem::swap(&mut self.len, &mut broken.len);
 
-        let new_node = Box::new(Node {
-            key: MaybeUninit::new(key),
-            value: MaybeUninit::new(value),
-            forward: vec![],
-            level,
-        });
+        for (key, value) in broken {
+            self.insert(key, value);
+        }
 
-        let mut new_node_ptr = NonNull::from(Box::leak(new_node));
+        true
+    }
 
-        for i in (0..=self.level).rev() {
-            let update_node = unsafe { update[i].as_mut() };
-            if i <= level {
-                let cur_span = step - steps[i];
+    /// Give every node a fresh random level drawn from `rng` and rebuild the
+    /// whole tower and all spans from scratch, keeping entries and their order.
+    ///
+    /// Nodes are relinked in place in one pass over level 0, independently of
+    /// the insert path, so lookups can be checked to be invariant under any
+    /// tower shape.
+    #[cfg(feature = "test-utils")]
+    pub fn rebuild_random_levels(&mut self, rng: &mut impl RngCore) {
+        let (probability, max_level) = (self.levels.probability, self.levels.cap(self.len));
+
+        let mut cur = unsafe { self.head.as_ref() }.forward[0].ptr;
+        unsafe { self.head.as_mut() }.forward.truncate(1);
+        self.level = 0;
+
+        // Last node seen on each level and its position (head is position 0)
+        let mut last = vec![self.head];
+        let mut last_pos = vec![0];
+        let mut pos = 0;
+
+        while !self.is_tail(cur) {
+            let node = unsafe { cur.as_mut() };
+            let next = node.forward[0].ptr;
+            let level = draw_level(rng, probability, max_level);
+            pos += 1;
+
+            while self.level < level {
+                unsafe { self.head.as_mut() }
+                    .forward
+                    .push(ForwardPtr::default());
+                last.push(self.head);
+                last_pos.push(0);
+                self.level += 1;
+            }
 
-                // println!(
-                //     "leve: {i}, step i: {}, updated_node span:{} ,step: {step}",
-                //     steps[i], update_node.forward[i].span
-                // );
-                forward[i] = ForwardPtr {
-                    ptr: update_node.forward[i].ptr,
-                    span: steps[i] + update_node.forward[i].span - step + 1,
-                };
-                // println!(
-                //     "span: {}",
-                //     steps[i] + update_node.forward[i].span - cur_span
-                // );
+            node.level = level;
+            node.forward = vec![ForwardPtr::default(); level + 1];
 
-                update_node.forward[i].ptr = new_node_ptr;
-                update_node.forward[i].span = cur_span;
-            } else {
-                update_node.forward[i].span += 1;
+            for i in 0..=level {
+                unsafe { last[i].as_mut() }.forward[i] = ForwardPtr {
+                    ptr: cur,
+                    span: pos - last_pos[i],
+                };
+                last[i] = cur;
+                last_pos[i] = pos;
             }
+
+            cur = next;
         }
 
-        //
-        // 1 2 3    (5)   7
-        // 1 2 3 4  (5) 6 7
+        for i in 0..=self.level {
+            unsafe { last[i].as_mut() }.forward[i] = ForwardPtr {
+                ptr: self.tail,
+                span: pos + 1 - last_pos[i],
+            };
+        }
+    }
 
-        unsafe { new_node_ptr.as_mut() }.forward = forward;
+    fn is_head(&self, node: NodePtr<K, V>) -> bool {
+        node == self.head
+    }
 
-        self.len += 1;
-        None
+    fn is_tail(&self, node: NodePtr<K, V>) -> bool {
+        node == self.tail
     }
 
-    pub fn remove<Q: fmt::Debug>(&mut self, key: &Q) -> Option<V>
-    where
-        K: Borrow<Q>,
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        self.insert_tracked(key, value).0
+    }
+
+    /// Like `insert`, additionally returning the tower height (top level) of
+    /// the node holding `key`: the freshly drawn one for a new key, the
+    /// existing node's for a replaced value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::SkipList;
+    ///
+    /// let mut skip_list = SkipList::new();
+    /// let (old, level) = skip_list.insert_tracked(1, "one");
+    /// assert_eq!(old, None);
+    ///
+    /// // Replacing keeps the node, and so its level
+    /// assert_eq!(skip_list.insert_tracked(1, "uno"), (Some("one"), level));
+    /// ```
+    pub fn insert_tracked(&mut self, key: K, value: V) -> (Option<V>, usize) {
+        let level = self.rand_level();
+        self.insert_at_level(key, value, level)
+    }
+
+    /// Insert every entry of `iter` like `Extend`, and return how many of
+    /// them added a new key rather than replacing a value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::SkipList;
+    ///
+    /// let mut skip_list = SkipList::from_sorted([(1, 'a'), (2, 'b')]);
+    ///
+    /// assert_eq!(skip_list.extend_counted([(2, 'x'), (3, 'y')]), 1);
+    /// assert_eq!(skip_list.len(), 3);
+    /// ```
+    pub fn extend_counted<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) -> usize {
+        let mut new_keys = 0;
+        for (key, value) in iter {
+            if self.insert(key, value).is_none() {
+                new_keys += 1;
+            }
+        }
+        new_keys
+    }
+
+    /// Insert with a caller-supplied tower level instead of a random one, so
+    /// the resulting structure is fully reproducible.
+    ///
+    /// If the key already exists its value is replaced and `level` is ignored.
+    #[cfg(any(test, feature = "test-utils"))]
+    pub fn insert_with_level(&mut self, key: K, value: V, level: usize) -> Option<V> {
+        self.insert_at_level(key, value, level).0
+    }
+
+    fn insert_at_level(&mut self, key: K, value: V, level: usize) -> (Option<V>, usize) {
+        let (mut update, mut steps) = self.find_update(&key);
+
+        let mut next = unsafe { update[0].as_ref() }.forward[0].ptr;
+
+        if !self.is_tail(next) && unsafe { next.as_ref() }.key() == &key {
+            // already exists, replace value
+            let old_v = std::mem::replace(unsafe { next.as_mut() }.value_mut(), value);
+
+            return (Some(old_v), unsafe { next.as_ref() }.level);
+        }
+
+        self.link_node(&mut update, &mut steps, key, value, level);
+
+        (None, level)
+    }
+
+    /// Descend from the head and record, on every level, the last node whose
+    /// key is less than `key` (`update`) together with its position (`steps`,
+    /// head is position 0).
+    fn find_update<Q>(&self, key: &Q) -> (Vec<NodePtr<K, V>>, Vec<usize>)
+    where
+        K: Borrow<Q>,
         Q: Ord + ?Sized,
     {
-        // println!("removing key: {key:?}");
-        let mut update = vec![NonNull::dangling(); self.level + 1];
+        let mut update = vec![NodePtr::dangling(); self.level + 1];
+        let mut steps = vec![0; self.level + 1];
+        let mut step = 0;
 
         let mut cur = self.head;
         for i in (0..=self.level).rev() {
             loop {
                 let cur_node_ref = unsafe { cur.as_ref() };
-                if self.is_tail(cur_node_ref.forward[i].ptr) {
+                let next = cur_node_ref.forward[i].ptr;
+
+                if self.is_tail(next) {
                     break;
                 }
-
-                let next_ptr = cur_node_ref.forward[i].ptr;
-                let next_key = unsafe { next_ptr.as_ref() }.key();
+                let next_key = (unsafe { next.as_ref() }).key();
                 if next_key.borrow() < key {
-                    cur = next_ptr;
+                    step += cur_node_ref.forward[i].span;
+                    cur = next;
                 } else {
                     break;
                 }
             }
             update[i] = cur;
+            steps[i] = step;
+        }
+
+        (update, steps)
+    }
+
+    /// Splice a new node with the given level in right after `update[0]`,
+    /// using the frontier captured by `find_update`.
+    ///
+    /// The frontier is extended with the head for any levels the list grows
+    /// by, so it stays a valid frontier for keys past the new one.
+    fn link_node(
+        &mut self,
+        update: &mut Vec<NodePtr<K, V>>,
+        steps: &mut Vec<usize>,
+        key: K,
+        value: V,
+        level: usize,
+    ) -> NodePtr<K, V> {
+        if level > self.level {
+            for _ in (self.level + 1)..=level {
+                unsafe {
+                    self.head.as_mut().forward.push(ForwardPtr {
+                        ptr: self.tail,
+                        span: self.len + 1,
+                    });
+                }
+                update.push(self.head);
+                steps.push(0);
+            }
+            self.level = level;
+        }
+
+        let step = steps[0] + 1;
+
+        let mut new_node_ptr = self.alloc_node(key, value, level);
+        let forward = &mut unsafe { new_node_ptr.as_mut() }.forward;
+
+        for i in (0..=self.level).rev() {
+            let update_node = unsafe { update[i].as_mut() };
+            if i <= level {
+                let (cur_span, next_span) = split_span(steps[i], update_node.forward[i].span, step);
+
+                forward[i] = ForwardPtr {
+                    ptr: update_node.forward[i].ptr,
+                    span: next_span,
+                };
+
+                update_node.forward[i].ptr = new_node_ptr;
+                update_node.forward[i].span = cur_span;
+            } else {
+                update_node.forward[i].span += 1;
+            }
         }
 
-        let next = unsafe { cur.as_ref() }.forward[0].ptr;
+        //
+        // 1 2 3    (5)   7
+        // 1 2 3 4  (5) 6 7
+
+        self.len += 1;
+
+        #[cfg(feature = "paranoid")]
+        self.assert_invariants();
+
+        new_node_ptr
+    }
+
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + fmt::Debug + ?Sized,
+    {
+        let (update, _) = self.find_update(key);
+
+        let next = unsafe { update[0].as_ref() }.forward[0].ptr;
         if self.is_tail(next) {
             return None;
         }
-        cur = next;
 
-        if unsafe { cur.as_ref() }.key().borrow() != key {
+        if unsafe { next.as_ref() }.key().borrow() != key {
             return None;
         }
 
-        let to_remove = cur;
+        let (_, value) = self.unlink_node(&update, next);
 
-        for i in (0..=self.level).rev() {
-            let update_node = unsafe { update[i].as_mut() };
+        Some(value)
+    }
 
-            unsafe {
-                if i <= to_remove.as_ref().level {
-                    update_node.forward[i] = ForwardPtr {
-                        ptr: cur.as_ref().forward[i].ptr,
-                        span: update[i].as_ref().forward[i].span
-                            + to_remove.as_ref().forward[i].span
-                            - 1,
-                    };
-                } else {
-                    update_node.forward[i].span -= 1;
+    /// Make every removal, from `remove` to `retain` and `remove_range`, call
+    /// `shrink_to_fit` whenever the tower has become more than two levels
+    /// taller than expected for the current length, e.g. after a mass
+    /// deletion spared mostly tall nodes. Off by default.
+    pub fn set_auto_shrink(&mut self, enabled: bool) {
+        self.auto_shrink = enabled;
+    }
+
+    /// Reserve room in the head tower for at least `additional` more levels,
+    /// reporting allocation failure instead of aborting, like
+    /// `Vec::try_reserve`. Neither the level nor the contents change.
+    pub fn try_reserve_levels(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        unsafe { self.head.as_mut() }
+            .forward
+            .try_reserve(additional)
+    }
+
+    /// Lower the tower to the height expected for the current length,
+    /// `ceil(log_{1/p}(len + 1))`, by cutting every node's tower above it,
+    /// and release spare capacity in the head.
+    ///
+    /// Lookups stay correct whatever the tower shape; this only bounds how
+    /// far a search has to descend.
+    ///
+    /// Time complexity: O(k) for the k nodes reaching above that height
+    pub fn shrink_to_fit(&mut self) {
+        let height = self.levels.expected_height(self.len);
+
+        if self.level > height {
+            // The nodes reaching above `height` are exactly those on the
+            // level right above it
+            let mut cur = unsafe { self.head.as_ref() }.forward[height + 1].ptr;
+            while !self.is_tail(cur) {
+                let node = unsafe { cur.as_mut() };
+                cur = node.forward[height + 1].ptr;
+                node.forward.truncate(height + 1);
+                node.forward.shrink_to_fit();
+                node.level = height;
+            }
+
+            unsafe { self.head.as_mut() }.forward.truncate(height + 1);
+            self.level = height;
+            self.trim_levels();
+        }
+
+        unsafe { self.head.as_mut() }.forward.shrink_to_fit();
+    }
+
+    /// Remove every present key of `sorted_keys`, returning how many were
+    /// removed. Keys that are not in the list are skipped.
+    ///
+    /// `sorted_keys` must be sorted in ascending order: the search for each key
+    /// resumes from the update frontier left by the previous one instead of
+    /// descending from the head again. Keys that are out of order are missed.
+    ///
+    /// Time complexity: O(m log(n / m)) expected for m keys
+    pub fn bulk_remove<Q>(&mut self, sorted_keys: &[Q]) -> usize
+    where
+        K: Borrow<Q>,
+        Q: Ord,
+    {
+        let mut update = vec![self.head; self.level + 1];
+        let mut steps = vec![0; self.level + 1];
+        let mut removed = 0;
+
+        for key in sorted_keys {
+            let mut cur = update[self.level];
+            let mut step = steps[self.level];
+
+            for i in (0..=self.level).rev() {
+                // Resume from whichever is further: the node we came down to or
+                // the previous key's predecessor on this level
+                if steps[i] > step {
+                    cur = update[i];
+                    step = steps[i];
+                }
+
+                loop {
+                    let forward_ptr = unsafe { cur.as_ref() }.forward[i];
+                    if self.is_tail(forward_ptr.ptr)
+                        || unsafe { forward_ptr.ptr.as_ref() }.key().borrow() >= key
+                    {
+                        break;
+                    }
+                    step += forward_ptr.span;
+                    cur = forward_ptr.ptr;
                 }
+                update[i] = cur;
+                steps[i] = step;
+            }
+
+            let next = unsafe { update[0].as_ref() }.forward[0].ptr;
+            if !self.is_tail(next) && unsafe { next.as_ref() }.key().borrow() == key {
+                // Nodes before the removed one keep their positions, so the
+                // frontier stays valid
+                self.unlink_node(&update, next);
+                update.truncate(self.level + 1);
+                steps.truncate(self.level + 1);
+                removed += 1;
             }
         }
 
-        // println!("after remove, before clean level:\n {}", self);
+        removed
+    }
 
-        let mut level_down = 0;
-        for i in (0..=self.level).rev() {
-            // println!("start cleaning at level {i}");
-            let head_next = unsafe { self.head.as_ref().forward[i].ptr };
+    /// Remove the first `n` entries, returning how many were removed (`n`
+    /// clamped to the length).
+    ///
+    /// The head is relinked past the removed run on every level in one pass,
+    /// instead of popping one entry at a time.
+    ///
+    /// Time complexity: O(log n + k) expected, where k is the number of
+    /// removed entries
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::SkipList;
+    ///
+    /// let mut skip_list = SkipList::from_sorted((0..10).map(|i| (i, i)));
+    /// assert_eq!(skip_list.remove_first_n(3), 3);
+    /// assert_eq!(skip_list.index(0), Some((&3, &3)));
+    /// ```
+    pub fn remove_first_n(&mut self, n: usize) -> usize {
+        let n = n.min(self.len);
+        if n == 0 {
+            return 0;
+        }
 
-            if self.is_tail(head_next) && i > 0 {
-                // println!("level down once at: {i}");
-                level_down += 1;
-                unsafe { self.head.as_mut() }.forward.pop();
-            } else {
+        let first = self.detach_range(0, n);
+        self.free_run(first, n);
+
+        n
+    }
+
+    /// Remove every entry, keeping the list's configuration.
+    ///
+    /// Time complexity: O(n)
+    pub fn clear(&mut self) {
+        self.remove_first_n(self.len);
+    }
+
+    /// Remove every entry but keep the node allocations for later inserts,
+    /// so that a list rebuilt after clearing does not allocate again.
+    ///
+    /// Keys and values are dropped right away. The allocations go to the
+    /// list's node pool, one shared with `with_pool` or, if there is none, a
+    /// private pool created here; from then on every removed node is kept
+    /// for reuse until the list is dropped.
+    ///
+    /// Time complexity: O(n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::SkipList;
+    ///
+    /// let mut skip_list = SkipList::from_sorted((0..100).map(|i| (i, i)));
+    /// skip_list.clear_retaining_capacity();
+    /// assert!(skip_list.is_empty());
+    ///
+    /// // Refilling takes the kept allocations instead of new ones
+    /// for i in 0..100 {
+    ///     skip_list.insert(i, i);
+    /// }
+    /// ```
+    pub fn clear_retaining_capacity(&mut self) {
+        self.pool.get_or_insert_with(NodePool::new);
+        self.clear();
+    }
+
+    /// Exchange the entire contents of the two lists, together with their
+    /// configuration (level settings, RNG, node pool and auto-shrink).
+    ///
+    /// Same as `std::mem::swap`, which is just as safe here: the head, tail
+    /// and every node live on the heap and nothing points back into the
+    /// `SkipList` struct itself, so moving the struct moves no node.
+    ///
+    /// Time complexity: O(1)
+    pub fn swap(&mut self, other: &mut Self) {
+        std::mem::swap(self, other);
+    }
+
+    /// Remove and return the leading entries for which `f` holds, stopping
+    /// at the first one for which it does not.
+    ///
+    /// `f` is called in key order and never again after it returns `false`.
+    /// The drained prefix is unlinked from every level in one pass, like
+    /// `remove_first_n`.
+    ///
+    /// Time complexity: O(log n + k) expected, where k is the number of
+    /// drained entries
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::SkipList;
+    ///
+    /// let mut timers = SkipList::from_sorted([(1, "a"), (3, "b"), (5, "c"), (7, "d")]);
+    /// let now = 5;
+    ///
+    /// assert_eq!(timers.drain_while(|&at, _| at < now), [(1, "a"), (3, "b")]);
+    /// assert_eq!(timers.len(), 2);
+    /// ```
+    pub fn drain_while<F>(&mut self, mut f: F) -> Vec<(K, V)>
+    where
+        F: FnMut(&K, &V) -> bool,
+    {
+        let mut n = 0;
+        let mut cur = unsafe { self.head.as_ref() }.forward[0].ptr;
+        while !self.is_tail(cur) {
+            let node = unsafe { cur.as_ref() };
+            if !f(node.key(), node.value()) {
                 break;
             }
+            n += 1;
+            cur = node.forward[0].ptr;
         }
 
-        self.level -= level_down;
+        if n == 0 {
+            return Vec::new();
+        }
 
-        self.len -= 1;
+        let mut cur = self.detach_range(0, n);
+        let mut drained = Vec::with_capacity(n);
+        for _ in 0..n {
+            let next = unsafe { cur.as_ref() }.forward[0].ptr;
+            drained.push(self.free_node(cur));
+            cur = next;
+        }
 
-        let node = unsafe { Box::from_raw(cur.as_ptr()) };
-        Some(unsafe { node.value.assume_init() })
+        drained
     }
 
-    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    /// Replace every key with `f(key)`.
+    ///
+    /// If `f` is strictly increasing over the keys, such as adding a constant
+    /// to integer keys, the order is preserved: the keys are overwritten in
+    /// place and every tower stays as it is. Otherwise the list is rebuilt by
+    /// re-inserting the entries under their new keys in the old key order,
+    /// so when two keys map to the same one the later entry's value wins.
+    ///
+    /// All new keys are computed before the list is touched, so it is left
+    /// unchanged if `f` panics.
+    ///
+    /// Time complexity: O(n) for an order-preserving `f`, O(n log n)
+    /// expected otherwise
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::SkipList;
+    ///
+    /// let mut skip_list = SkipList::from_sorted([(1, 'a'), (2, 'b')]);
+    /// skip_list.remap_keys(|k| k + 100);
+    ///
+    /// assert_eq!(skip_list.get(&101), Some(&'a'));
+    /// assert_eq!(skip_list.get(&1), None);
+    /// ```
+    pub fn remap_keys<F: FnMut(&K) -> K>(&mut self, mut f: F) {
+        let keys: Vec<K> = self.iter().map(|(key, _)| f(key)).collect();
+
+        if keys.is_sorted_by(|a, b| a < b) {
+            let mut cur = unsafe { self.head.as_ref() }.forward[0].ptr;
+            for key in keys {
+                let node = unsafe { cur.as_mut() };
+                *node.key_mut() = key;
+                cur = node.forward[0].ptr;
+            }
+            return;
+        }
+
+        let len = self.len;
+        let mut cur = self.detach_range(0, len);
+        let mut entries = Vec::with_capacity(len);
+        for key in keys {
+            let next = unsafe { cur.as_ref() }.forward[0].ptr;
+            let (_, value) = self.free_node(cur);
+            entries.push((key, value));
+            cur = next;
+        }
+
+        for (key, value) in entries {
+            self.insert(key, value);
+        }
+    }
+
+    /// Unlink the `n` nodes (at least one) from index `start` on from every
+    /// level by pointing the nodes before them past them, and return the
+    /// first of them. The detached run is still chained on level 0.
+    ///
+    /// The nodes before and the last detached node on every level are
+    /// found by one span-guided descent each.
+    fn detach_range(&mut self, start: usize, n: usize) -> NodePtr<K, V> {
+        let (before, before_steps) = self.position_frontier(start);
+        let (last, last_steps) = self.position_frontier(start + n);
+        let first = unsafe { before[0].as_ref() }.forward[0].ptr;
+
+        for i in 0..=self.level {
+            // `before[i]` is `last[i]` when no detached node reaches level i
+            let next = unsafe { last[i].as_ref() }.forward[i];
+            let mut node = before[i];
+            unsafe { node.as_mut() }.forward[i] = ForwardPtr {
+                ptr: next.ptr,
+                span: last_steps[i] + next.span - n - before_steps[i],
+            };
+        }
+
+        self.len -= n;
+        self.trim_levels();
+        self.after_removal();
+
+        first
+    }
+
+    /// Remove the last `n` entries, returning how many were removed (`n`
+    /// clamped to the length).
+    ///
+    /// A span-guided descent finds the last kept node on every level, and
+    /// each of them is pointed straight at the tail.
+    ///
+    /// Time complexity: O(log n + k) expected, where k is the number of
+    /// removed entries
+    pub fn remove_last_n(&mut self, n: usize) -> usize {
+        let n = n.min(self.len);
+        if n == 0 {
+            return 0;
+        }
+
+        let kept = self.len - n;
+        let (update, steps) = self.position_frontier(kept);
+        let first = unsafe { update[0].as_ref() }.forward[0].ptr;
+
+        for i in 0..=self.level {
+            let mut last = update[i];
+            unsafe { last.as_mut() }.forward[i] = ForwardPtr {
+                ptr: self.tail,
+                span: kept + 1 - steps[i],
+            };
+        }
+
+        self.len = kept;
+        self.trim_levels();
+        self.after_removal();
+
+        self.free_run(first, n);
+
+        n
+    }
+
+    /// Remove every entry whose key falls in `range`, returning how many
+    /// were removed.
+    ///
+    /// The run is unlinked from every level at once: two descents find the
+    /// nodes on both sides of it, and the removed nodes are only visited to
+    /// free them.
+    ///
+    /// Time complexity: O(log n + k) expected, where k is the number of
+    /// removed entries
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::SkipList;
+    ///
+    /// let mut skip_list = SkipList::from_sorted((0..10).map(|i| (i, i)));
+    ///
+    /// assert_eq!(skip_list.remove_range(3..7), 4);
+    /// assert_eq!(skip_list.index(3), Some((&7, &7)));
+    /// ```
+    pub fn remove_range<Q, R>(&mut self, range: R) -> usize
     where
         K: Borrow<Q>,
         Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
     {
-        let mut cur = self.head;
-        for i in (0..=self.level).rev() {
-            loop {
-                let next = unsafe { cur.as_ref() }.forward[i].ptr;
-                if self.is_tail(next) {
-                    break;
-                }
-                let next_key = (unsafe { next.as_ref() }).key();
-
-                if next_key.borrow() == key {
-                    return Some(unsafe { next.as_ref().value() });
-                }
-
-                if next_key.borrow() < key {
-                    cur = next;
-                } else {
-                    break;
-                }
-            }
+        let ((_, start), (_, end)) = self.range_nodes(range);
+        let n = end - start;
+        if n > 0 {
+            let first = self.detach_range(start, n);
+            self.free_run(first, n);
         }
 
-        None
+        n
     }
 
-    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    /// Replace the entries whose keys fall in `range` with `new_entries`,
+    /// like a delete followed by an insert of the new rows.
+    ///
+    /// The new entries are meant to lie within `range`, but this is not
+    /// checked: any outside it are inserted like by `insert`, replacing the
+    /// value of an existing equal key. Among equal new keys the last wins.
+    ///
+    /// Time complexity: O(log n + k + m log n) expected, where k is the
+    /// number of removed and m the number of new entries
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::SkipList;
+    ///
+    /// let mut skip_list = SkipList::from_sorted((0..5).map(|i| (i * 10, "old")));
+    /// skip_list.replace_range(10..30, [(15, "new"), (25, "new")]);
+    ///
+    /// let entries: Vec<_> = skip_list.iter().map(|(&k, &v)| (k, v)).collect();
+    /// assert_eq!(
+    ///     entries,
+    ///     vec![(0, "old"), (15, "new"), (25, "new"), (30, "old"), (40, "old")]
+    /// );
+    /// ```
+    pub fn replace_range<Q, R, I>(&mut self, range: R, new_entries: I)
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+        I: IntoIterator<Item = (K, V)>,
+    {
+        self.remove_range(range);
+        self.extend(new_entries);
+    }
+
+    /// Remove the entry at `index`, returning it together with the index of
+    /// the entry that now occupies the freed slot: `index` itself if an entry
+    /// followed the removed one, `None` if the last entry was removed.
+    ///
+    /// Time complexity: O(log n) expected
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::SkipList;
+    ///
+    /// let mut skip_list = SkipList::from_sorted([(1, 'a'), (2, 'b'), (3, 'c')]);
+    ///
+    /// assert_eq!(skip_list.remove_index_with_neighbor(1), Some((2, 'b', Some(1))));
+    /// assert_eq!(skip_list.remove_index_with_neighbor(1), Some((3, 'c', None)));
+    /// assert_eq!(skip_list.remove_index_with_neighbor(1), None);
+    /// ```
+    pub fn remove_index_with_neighbor(&mut self, index: usize) -> Option<(K, V, Option<usize>)> {
+        if index >= self.len {
+            return None;
+        }
+
+        let (update, _) = self.position_frontier(index);
+        let node = unsafe { update[0].as_ref() }.forward[0].ptr;
+        let (key, value) = self.unlink_node(&update, node);
+
+        let neighbor = (index < self.len).then_some(index);
+        Some((key, value, neighbor))
+    }
+
+    /// Last node at or before position `pos` on every level, with the
+    /// position of each (head is position 0).
+    fn position_frontier(&self, pos: usize) -> (Vec<NodePtr<K, V>>, Vec<usize>) {
+        let mut update = vec![self.head; self.level + 1];
+        let mut steps = vec![0; self.level + 1];
+        let mut step = 0;
+
+        let mut cur = self.head;
+        for i in (0..=self.level).rev() {
+            loop {
+                let forward = unsafe { cur.as_ref() }.forward[i];
+                if self.is_tail(forward.ptr) || step + forward.span > pos {
+                    break;
+                }
+                step += forward.span;
+                cur = forward.ptr;
+            }
+            update[i] = cur;
+            steps[i] = step;
+        }
+
+        (update, steps)
+    }
+
+    /// Free `count` nodes that were already unlinked, following level 0 from
+    /// `first`.
+    fn free_run(&self, mut cur: NodePtr<K, V>, count: usize) {
+        for _ in 0..count {
+            let next = unsafe { cur.as_ref() }.forward[0].ptr;
+            self.free_node(cur);
+            cur = next;
+        }
+    }
+
+    /// Splice `to_remove` out of every level, given the predecessors captured
+    /// by `find_update`, and hand back its key and value.
+    fn unlink_node(&mut self, update: &[NodePtr<K, V>], to_remove: NodePtr<K, V>) -> (K, V) {
+        for i in (0..=self.level).rev() {
+            let mut update_ptr = update[i];
+            let update_node = unsafe { update_ptr.as_mut() };
+
+            unsafe {
+                if i <= to_remove.as_ref().level {
+                    update_node.forward[i] = ForwardPtr {
+                        ptr: to_remove.as_ref().forward[i].ptr,
+                        span: join_spans(
+                            update_node.forward[i].span,
+                            to_remove.as_ref().forward[i].span,
+                        ),
+                    };
+                } else {
+                    update_node.forward[i].span = shrink_span(update_node.forward[i].span);
+                }
+            }
+        }
+
+        self.trim_levels();
+
+        self.len -= 1;
+        self.after_removal();
+
+        self.free_node(to_remove)
+    }
+
+    /// Common tail of every removal path, once the removed nodes are
+    /// unlinked and `len` is updated: auto-shrink, then the `paranoid`
+    /// check.
+    fn after_removal(&mut self) {
+        // Shrink only once the tower is clearly too tall, so that a list
+        // hovering around one size does not keep rebuilding its top levels
+        if self.auto_shrink && self.level > self.levels.expected_height(self.len) + 2 {
+            self.shrink_to_fit();
+        }
+
+        // Not while unwinding, e.g. from a `retain` callback: a failed check
+        // would abort and hide the original panic
+        #[cfg(feature = "paranoid")]
+        if !std::thread::panicking() {
+            self.assert_invariants();
+        }
+    }
+
+    /// Drop the topmost levels that no longer hold any node.
+    fn trim_levels(&mut self) {
+        let mut level_down = 0;
+        for i in (0..=self.level).rev() {
+            let head_next = unsafe { self.head.as_ref().forward[i].ptr };
+
+            if self.is_tail(head_next) && i > 0 {
+                level_down += 1;
+                unsafe { self.head.as_mut() }.forward.pop();
+            } else {
+                break;
+            }
+        }
+
+        self.level -= level_down;
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.find_node(key)
+            .map(|node| unsafe { node.as_ref() }.value())
+    }
+
+    /// Clone of the value stored under `key`, for callers that must not keep
+    /// the list borrowed, such as one behind a lock.
+    pub fn get_cloned<Q>(&self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        V: Clone,
+    {
+        self.get(key).cloned()
+    }
+
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.find_node(key)
+            .map(|mut node| unsafe { node.as_mut() }.value_mut())
+    }
+
+    /// Get mutable references to the values of two distinct keys at once.
+    ///
+    /// Returns `None` if either key is missing or if both keys are equal.
+    /// Both nodes are located in one descent: the two searches share their
+    /// path until it forks between `a` and `b`.
+    ///
+    /// Time complexity: O(log n) expected
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::SkipList;
+    ///
+    /// let mut skip_list = SkipList::from_sorted([(1, "one"), (2, "two")]);
+    ///
+    /// if let Some((a, b)) = skip_list.get_pair_mut(&1, &2) {
+    ///     std::mem::swap(a, b);
+    /// }
+    /// assert_eq!(skip_list.get(&1), Some(&"two"));
+    /// assert_eq!(skip_list.get(&2), Some(&"one"));
+    /// ```
+    pub fn get_pair_mut<Q>(&mut self, a: &Q, b: &Q) -> Option<(&mut V, &mut V)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let (lo, hi) = match a.cmp(b) {
+            std::cmp::Ordering::Less => (a, b),
+            std::cmp::Ordering::Greater => (b, a),
+            std::cmp::Ordering::Equal => return None,
+        };
+
+        // Last node on the current level whose key is below `bound`
+        let advance = |mut cur: NodePtr<K, V>, level: usize, bound: &Q| loop {
+            let next = unsafe { cur.as_ref() }.forward[level].ptr;
+            if self.is_tail(next) || unsafe { next.as_ref() }.key().borrow() >= bound {
+                break cur;
+            }
+            cur = next;
+        };
+
+        let mut before_lo = self.head;
+        let mut before_hi = self.head;
+        for i in (0..=self.level).rev() {
+            // While the paths have not forked, the `hi` search picks up
+            // where the `lo` one stopped
+            let shared = before_lo == before_hi;
+            before_lo = advance(before_lo, i, lo);
+            if shared {
+                before_hi = before_lo;
+            }
+            before_hi = advance(before_hi, i, hi);
+        }
+
+        let found = |before: NodePtr<K, V>, key: &Q| {
+            let next = unsafe { before.as_ref() }.forward[0].ptr;
+            (!self.is_tail(next) && unsafe { next.as_ref() }.key().borrow() == key).then_some(next)
+        };
+        let mut lo_node = found(before_lo, lo)?;
+        let mut hi_node = found(before_hi, hi)?;
+
+        // The keys differ, so the nodes are distinct and the borrows disjoint
+        let lo_value = unsafe { lo_node.as_mut() }.value_mut();
+        let hi_value = unsafe { hi_node.as_mut() }.value_mut();
+        if a < b {
+            Some((lo_value, hi_value))
+        } else {
+            Some((hi_value, lo_value))
+        }
+    }
+
+    /// Look up every key in `keys`, returning their values in the same order.
+    ///
+    /// When `keys` is sorted (duplicates allowed) the lookups share a single
+    /// forward pass: each search starts from the previous key's predecessors
+    /// and climbs only as many levels as the gap between the two keys needs,
+    /// instead of descending from the head again. Unsorted input falls back
+    /// to one `get` per key.
+    ///
+    /// Time complexity: O(m log n) expected, where m is `keys.len()`; for
+    /// sorted input, O(m log(n / m)) expected
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::SkipList;
+    ///
+    /// let skip_list = SkipList::from_sorted([(1, "one"), (2, "two"), (3, "three")]);
+    ///
+    /// assert_eq!(skip_list.get_batch(&[1, 3, 4]), [Some(&"one"), Some(&"three"), None]);
+    /// assert_eq!(skip_list.get_batch(&[3, 1]), [Some(&"three"), Some(&"one")]);
+    /// ```
+    pub fn get_batch<Q>(&self, keys: &[Q]) -> Vec<Option<&V>>
+    where
+        K: Borrow<Q>,
+        Q: Ord,
+    {
+        if !keys.is_sorted() {
+            return keys.iter().map(|key| self.get(key)).collect();
+        }
+
+        let mut update = vec![self.head; self.level + 1];
+        let mut steps = vec![0; self.level + 1];
+
+        // Whether `key` lies beyond the next node after the frontier on `level`
+        let passes = |update: &[NodePtr<K, V>], level: usize, key: &Q| {
+            let next = unsafe { update[level].as_ref() }.forward[level].ptr;
+            !self.is_tail(next) && unsafe { next.as_ref() }.key().borrow() < key
+        };
+
+        keys.iter()
+            .map(|key| {
+                // Climb only as high as needed to step over the gap from the
+                // previous key; the frontier above that level is still valid
+                let mut top = 0;
+                while top < self.level && passes(&update, top, key) {
+                    top += 1;
+                }
+
+                let mut cur = update[top];
+                let mut step = steps[top];
+
+                for i in (0..=top).rev() {
+                    // Resume from whichever is further: the node we came down
+                    // to or the previous key's predecessor on this level
+                    if steps[i] > step {
+                        cur = update[i];
+                        step = steps[i];
+                    }
+
+                    loop {
+                        let forward_ptr = unsafe { cur.as_ref() }.forward[i];
+                        if self.is_tail(forward_ptr.ptr)
+                            || unsafe { forward_ptr.ptr.as_ref() }.key().borrow() >= key
+                        {
+                            break;
+                        }
+                        step += forward_ptr.span;
+                        cur = forward_ptr.ptr;
+                    }
+                    update[i] = cur;
+                    steps[i] = step;
+                }
+
+                let next = unsafe { update[0].as_ref() }.forward[0].ptr;
+                (!self.is_tail(next) && unsafe { next.as_ref() }.key().borrow() == key)
+                    .then(|| unsafe { next.as_ref() }.value())
+            })
+            .collect()
+    }
+
+    /// Tower height (the node's top level) of the node stored under `key`.
+    #[cfg(feature = "test-utils")]
+    pub fn level_of<Q>(&self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.find_node(key)
+            .map(|node| unsafe { node.as_ref() }.level)
+    }
+
+    /// Locate the node holding `key`, stopping as soon as it is seen on any level.
+    fn find_node<Q>(&self, key: &Q) -> Option<NodePtr<K, V>>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let mut cur = self.head;
+        for i in (0..=self.level).rev() {
+            loop {
+                let next = unsafe { cur.as_ref() }.forward[i].ptr;
+                if self.is_tail(next) {
+                    break;
+                }
+                let next_key = (unsafe { next.as_ref() }).key();
+
+                if next_key.borrow() == key {
+                    return Some(next);
+                }
+
+                if next_key.borrow() < key {
+                    cur = next;
+                } else {
+                    break;
+                }
+            }
+        }
+
+        None
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.get(key).is_some()
+    }
+
+    /// Count the keys that are less than or equal to `key`.
+    ///
+    /// Time complexity: O(log n) expected
+    pub fn count_le<Q>(&self, key: &Q) -> usize
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.seek_while(|k| k.borrow() <= key).1
+    }
+
+    /// Count the keys that are strictly less than `key`.
+    ///
+    /// Time complexity: O(log n) expected
+    pub fn count_lt<Q>(&self, key: &Q) -> usize
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.seek_while(|k| k.borrow() < key).1
+    }
+
+    /// Count the keys that start with `prefix`; all of them for an empty
+    /// prefix.
+    ///
+    /// Keys sharing a prefix are contiguous in sorted order, so this is the
+    /// difference of two span-summing descents: one past the keys below
+    /// `prefix`, one past those that are below it or start with it. No
+    /// successor string is built, so a prefix ending in `char::MAX` needs no
+    /// special handling.
+    ///
+    /// Time complexity: O(log n) expected
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::SkipList;
+    ///
+    /// let skip_list = SkipList::from_sorted(["apple", "banana", "band", "bar", "cat"].map(|k| (k, ())));
+    ///
+    /// assert_eq!(skip_list.count_with_prefix("ba"), 3);
+    /// assert_eq!(skip_list.count_with_prefix("ban"), 2);
+    /// assert_eq!(skip_list.count_with_prefix(""), 5);
+    /// ```
+    pub fn count_with_prefix(&self, prefix: &str) -> usize
+    where
+        K: Borrow<str>,
+    {
+        let below = self.seek_while(|k| k.borrow() < prefix).1;
+        let through = self
+            .seek_while(|k| {
+                let k: &str = k.borrow();
+                k < prefix || k.starts_with(prefix)
+            })
+            .1;
+
+        through - below
+    }
+
+    /// 0-based position of `key`, or `None` if it is not in the list.
+    ///
+    /// Time complexity: O(log n) expected
+    pub fn rank<Q>(&self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let (node, position) = self.lower_bound_node(key);
+        (!self.is_tail(node) && unsafe { node.as_ref() }.key().borrow() == key).then_some(position)
+    }
+
+    /// Whether `key` is present and, if so, where: its 0-based position, or
+    /// `None` if it is missing. Same as `rank`.
+    ///
+    /// Unlike `lower_bound_index`, a missing key never yields a position.
+    ///
+    /// Time complexity: O(log n) expected
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::SkipList;
+    ///
+    /// let skip_list = SkipList::from_sorted([(10, ()), (20, ()), (30, ())]);
+    ///
+    /// assert_eq!(skip_list.find(&20), Some(1));
+    /// assert_eq!(skip_list.find(&25), None);
+    /// assert_eq!(skip_list.lower_bound_index(&25), 2);
+    /// ```
+    pub fn find<Q>(&self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.rank(key)
+    }
+
+    /// Position at which `key` is or would be inserted: the number of keys
+    /// less than it, from 0 up to `len()`. Same as `count_lt`.
+    ///
+    /// This is an insertion point, so it is returned whether or not `key` is
+    /// present; use `find` to tell the two cases apart.
+    ///
+    /// Time complexity: O(log n) expected
+    pub fn lower_bound_index<Q>(&self, key: &Q) -> usize
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.count_lt(key)
+    }
+
+    /// Binary search with a custom comparator: find the entry for which
+    /// `pred` returns `Equal`, along with its position.
+    ///
+    /// `pred` reports how an entry compares to the target, like the closure
+    /// of `slice::binary_search_by`, and must be monotone over the sorted
+    /// order: `Less` for a prefix of the entries, then `Equal`, then
+    /// `Greater`. If several entries compare `Equal`, the first one is
+    /// returned.
+    ///
+    /// Time complexity: O(log n) expected
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::SkipList;
+    ///
+    /// let skip_list = SkipList::from_sorted((0..10).map(|i| (i, i * i)));
+    ///
+    /// assert_eq!(skip_list.select_by(|_, v| v.cmp(&49)), Some((7, &7, &49)));
+    /// assert_eq!(skip_list.select_by(|_, v| v.cmp(&50)), None);
+    /// ```
+    pub fn select_by<P>(&self, mut pred: P) -> Option<(usize, &K, &V)>
+    where
+        P: FnMut(&K, &V) -> std::cmp::Ordering,
+    {
+        let (next, position) =
+            self.seek_while_entry(|key, value| pred(key, value) == std::cmp::Ordering::Less);
+        if self.is_tail(next) {
+            return None;
+        }
+        let next = unsafe { next.as_ref() };
+        (pred(next.key(), next.value()) == std::cmp::Ordering::Equal).then_some((
+            position,
+            next.key(),
+            next.value(),
+        ))
+    }
+
+    /// Binary search for `value`, assuming the values are sorted in key
+    /// order, which the caller must ensure.
+    ///
+    /// Mirrors `slice::binary_search`: returns `Ok` with the position of an
+    /// entry holding `value`, or `Err` with the position where it would have
+    /// to go to keep the values sorted. If the values are not sorted, the
+    /// result is unspecified but still a valid position.
+    ///
+    /// Time complexity: O(log n) expected
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::SkipList;
+    ///
+    /// let skip_list = SkipList::from_sorted([(1, 10), (2, 20), (3, 30)]);
+    ///
+    /// assert_eq!(skip_list.search_sorted_value(&20), Ok(1));
+    /// assert_eq!(skip_list.search_sorted_value(&25), Err(2));
+    /// ```
+    pub fn search_sorted_value<Q>(&self, value: &Q) -> Result<usize, usize>
+    where
+        V: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let (next, position) = self.seek_while_entry(|_, v| v.borrow() < value);
+        if !self.is_tail(next) && unsafe { next.as_ref() }.value().borrow() == value {
+            Ok(position)
+        } else {
+            Err(position)
+        }
+    }
+
+    /// Position of the largest key that is less than or equal to `key`, or
+    /// `None` if every key is greater.
+    ///
+    /// Time complexity: O(log n) expected
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::SkipList;
+    ///
+    /// let skip_list = SkipList::from_sorted([(10, ()), (20, ()), (30, ())]);
+    ///
+    /// assert_eq!(skip_list.floor_index(&25), Some(1));
+    /// assert_eq!(skip_list.floor_index(&30), Some(2));
+    /// assert_eq!(skip_list.floor_index(&5), None);
+    /// ```
+    pub fn floor_index<Q>(&self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.count_le(key).checked_sub(1)
+    }
+
+    /// Position of the smallest key that is greater than or equal to `key`,
+    /// or `None` if every key is less.
+    ///
+    /// Time complexity: O(log n) expected
+    pub fn ceiling_index<Q>(&self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let position = self.count_lt(key);
+        (position < self.len).then_some(position)
+    }
+
+    /// The entry whose key is closest to `key`, or `None` if the list is
+    /// empty. On a tie the smaller key wins.
+    ///
+    /// Works for unsigned keys too: the distance is always taken as the
+    /// larger key minus the smaller one.
+    ///
+    /// Time complexity: O(log n) expected
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::SkipList;
+    ///
+    /// let skip_list = SkipList::from_sorted([(1u32, 'a'), (5, 'b'), (9, 'c')]);
+    ///
+    /// assert_eq!(skip_list.nearest(&6), Some((&5, &'b')));
+    /// assert_eq!(skip_list.nearest(&8), Some((&9, &'c')));
+    /// assert_eq!(skip_list.nearest(&7), Some((&5, &'b')));
+    /// ```
+    pub fn nearest(&self, key: &K) -> Option<(&K, &V)>
+    where
+        K: Clone + Sub<Output = K>,
+    {
+        self.nearest_by(key, |a, b| {
+            if a < b {
+                b.clone() - a.clone()
+            } else {
+                a.clone() - b.clone()
+            }
+        })
+    }
+
+    /// The entry whose key is closest to `key` by `dist`, or `None` if the
+    /// list is empty. On a tie the smaller key wins.
+    ///
+    /// Only the floor and the ceiling of `key` are compared, so `dist` must
+    /// grow as keys move away from `key` in either direction.
+    ///
+    /// Time complexity: O(log n) expected
+    pub fn nearest_by<D, F>(&self, key: &K, dist: F) -> Option<(&K, &V)>
+    where
+        D: PartialOrd,
+        F: Fn(&K, &K) -> D,
+    {
+        let (ceiling, position) = self.lower_bound_node(key);
+        let ceiling = (!self.is_tail(ceiling)).then_some(unsafe { ceiling.as_ref() });
+        if let Some(ceiling) = ceiling
+            && ceiling.key() == key
+        {
+            return Some((ceiling.key(), ceiling.value()));
+        }
+
+        let floor = position
+            .checked_sub(1)
+            .and_then(|position| self.node_at(position))
+            .map(|floor| unsafe { floor.as_ref() });
+
+        let node = match (floor, ceiling) {
+            (Some(floor), Some(ceiling)) => {
+                if dist(key, ceiling.key()) < dist(key, floor.key()) {
+                    ceiling
+                } else {
+                    floor
+                }
+            }
+            (floor, ceiling) => floor.or(ceiling)?,
+        };
+
+        Some((node.key(), node.value()))
+    }
+
+    /// First node whose key is not less than `key` (the tail if there is
+    /// none), along with its 0-based position.
+    fn lower_bound_node<Q>(&self, key: &Q) -> PositionedNode<K, V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.seek_while(|k| k.borrow() < key)
+    }
+
+    /// First node whose key is greater than `key` (the tail if there is
+    /// none), along with its 0-based position.
+    fn upper_bound_node<Q>(&self, key: &Q) -> PositionedNode<K, V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.seek_while(|k| k.borrow() <= key)
+    }
+
+    /// First node whose key does not satisfy `pred` (the tail if there is
+    /// none) and its 0-based position, which is also the length of the prefix
+    /// satisfying `pred`. Found with a single descent that sums the spans it
+    /// skips over; `pred` must be true for a prefix of the keys and false for
+    /// the rest.
+    fn seek_while<F: Fn(&K) -> bool>(&self, pred: F) -> PositionedNode<K, V> {
+        self.seek_while_entry(|key, _| pred(key))
+    }
+
+    /// Same as `seek_while`, with `pred` also looking at the values.
+    fn seek_while_entry<F>(&self, mut pred: F) -> PositionedNode<K, V>
+    where
+        F: FnMut(&K, &V) -> bool,
+    {
+        let mut position = 0;
+        let mut cur = self.head;
+        for i in (0..=self.level).rev() {
+            loop {
+                let forward_ptr = unsafe { cur.as_ref() }.forward[i];
+                if self.is_tail(forward_ptr.ptr) {
+                    break;
+                }
+                let next = unsafe { forward_ptr.ptr.as_ref() };
+                if !pred(next.key(), next.value()) {
+                    break;
+                }
+                position += forward_ptr.span;
+                cur = forward_ptr.ptr;
+            }
+        }
+
+        (unsafe { cur.as_ref() }.forward[0].ptr, position)
+    }
+
+    /// Resolve `range` to the first node inside it and the first node past it,
+    /// each with its position. Empty and reversed ranges resolve to a start
+    /// equal to the end.
+    fn range_nodes<Q, R>(&self, range: R) -> (PositionedNode<K, V>, PositionedNode<K, V>)
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        let start = match range.start_bound() {
+            Bound::Included(key) => self.lower_bound_node(key),
+            Bound::Excluded(key) => self.upper_bound_node(key),
+            Bound::Unbounded => (unsafe { self.head.as_ref() }.forward[0].ptr, 0),
+        };
+        let end = match range.end_bound() {
+            Bound::Included(key) => self.upper_bound_node(key),
+            Bound::Excluded(key) => self.lower_bound_node(key),
+            Bound::Unbounded => (self.tail, self.len),
+        };
+
+        if end.1 <= start.1 {
+            (end, end)
+        } else {
+            (start, end)
+        }
+    }
+
+    /// Get the key-value pair at the specified index using span information for efficient traversal.
+    /// Returns None if the index is out of bounds.
+    ///
+    /// Time complexity: O(log n) expected
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::SkipList;
+    ///
+    /// let mut skip_list = SkipList::new();
+    /// skip_list.insert(1, "first");
+    /// skip_list.insert(2, "second");
+    /// skip_list.insert(3, "third");
+    ///
+    /// assert_eq!(skip_list.index(0), Some((&1, &"first")));
+    /// assert_eq!(skip_list.index(1), Some((&2, &"second")));
+    /// assert_eq!(skip_list.index(2), Some((&3, &"third")));
+    /// assert_eq!(skip_list.index(3), None);
+    /// ```
+    pub fn index(&self, index: usize) -> Option<(&K, &V)> {
+        self.node_at(index).map(|node| {
+            let node = unsafe { node.as_ref() };
+            (node.key(), node.value())
+        })
+    }
+
+    /// A uniformly random entry, or `None` if the list is empty.
+    ///
+    /// A position is drawn from `rng` and looked up with `index`.
+    ///
+    /// Time complexity: O(log n) expected
+    pub fn sample(&self, rng: &mut impl RngCore) -> Option<(&K, &V)> {
+        if self.len == 0 {
+            return None;
+        }
+
+        self.index(rng.random_range(0..self.len))
+    }
+
+    /// `n` distinct entries chosen uniformly at random, without
+    /// replacement, in sorted order. Every entry is returned if `n` is at
+    /// least the length.
+    ///
+    /// Time complexity: O(n log len) expected
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rand::{SeedableRng, rngs::StdRng};
+    /// use skiplist::SkipList;
+    ///
+    /// let skip_list = SkipList::from_sorted((0..100).map(|i| (i, i * i)));
+    /// let picked = skip_list.sample_many(5, &mut StdRng::seed_from_u64(7));
+    ///
+    /// assert_eq!(picked.len(), 5);
+    /// assert!(picked.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    /// ```
+    pub fn sample_many(&self, n: usize, rng: &mut impl RngCore) -> Vec<(&K, &V)> {
+        let mut positions = rand::seq::index::sample(rng, self.len, n.min(self.len)).into_vec();
+        positions.sort_unstable();
+
+        positions
+            .into_iter()
+            .filter_map(|position| self.index(position))
+            .collect()
+    }
+
+    /// Get a mutable reference to the value at the specified index.
+    /// Returns None if the index is out of bounds.
+    ///
+    /// Time complexity: O(log n) expected
+    pub fn index_mut(&mut self, index: usize) -> Option<(&K, &mut V)> {
+        self.node_at(index).map(|mut current| {
+            let key_ref = unsafe { current.as_ref() }.key(); // Get immutable reference to key
+            let value_ref = unsafe { current.as_mut() }.value_mut(); // Get mutable reference to value
+            (key_ref, value_ref)
+        })
+    }
+
+    /// Set the value at the specified index, returning the old one, or
+    /// `None` (dropping `value`) if the index is out of bounds.
+    ///
+    /// Time complexity: O(log n) expected
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::SkipList;
+    ///
+    /// let mut skip_list = SkipList::from_sorted([(1, "a"), (2, "b")]);
+    ///
+    /// assert_eq!(skip_list.set_index(1, "B"), Some("b"));
+    /// assert_eq!(skip_list.index(1), Some((&2, &"B")));
+    /// assert_eq!(skip_list.set_index(2, "c"), None);
+    /// ```
+    pub fn set_index(&mut self, index: usize, value: V) -> Option<V> {
+        self.index_mut(index)
+            .map(|(_, old)| std::mem::replace(old, value))
+    }
+
+    /// Replace the value of every entry whose key falls in `range` with
+    /// `f(key)`, keeping the keys and the structure untouched.
+    ///
+    /// Time complexity: O(log n + k) expected, where k is the number of
+    /// entries in the range
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::SkipList;
+    ///
+    /// let mut skip_list = SkipList::from_sorted((0..5).map(|i| (i, i + 1)));
+    /// skip_list.reset_range(1..3, |_| 0);
+    ///
+    /// let values: Vec<_> = skip_list.iter().map(|(_, &v)| v).collect();
+    /// assert_eq!(values, vec![1, 0, 0, 4, 5]);
+    /// ```
+    pub fn reset_range<Q, R, F>(&mut self, range: R, mut f: F)
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+        F: FnMut(&K) -> V,
+    {
+        let ((mut current, start_pos), (_, end_pos)) = self.range_nodes(range);
+
+        for _ in start_pos..end_pos {
+            let node = unsafe { current.as_mut() };
+            *node.value_mut() = f(node.key());
+            current = node.forward[0].ptr;
+        }
+    }
+
+    /// Get mutable references to the `N` consecutive values starting at
+    /// position `start`, or `None` if `start + N` exceeds the length.
+    ///
+    /// Time complexity: O(log n + N) expected
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::SkipList;
+    ///
+    /// let mut skip_list = SkipList::from_sorted((0..10).map(|i| (i, i)));
+    ///
+    /// let [a, b, c] = skip_list.index_range_array_mut::<3>(4).unwrap();
+    /// *a *= 10;
+    /// *b *= 10;
+    /// *c *= 10;
+    /// assert_eq!(skip_list.index(5), Some((&5, &50)));
+    /// ```
+    pub fn index_range_array_mut<const N: usize>(&mut self, start: usize) -> Option<[&mut V; N]> {
+        if start.checked_add(N)? > self.len {
+            return None;
+        }
+        if N == 0 {
+            // `start` may be `len`, where there is no node to seek to
+            return Some(std::array::from_fn(|_| unreachable!()));
+        }
+
+        let mut current = self.node_at(start)?;
+        // Each node is visited once, so the references never alias
+        Some(std::array::from_fn(|_| {
+            let mut node = current;
+            current = unsafe { node.as_ref() }.forward[0].ptr;
+            unsafe { node.as_mut() }.value_mut()
+        }))
+    }
+
+    /// Get mutable references to the values of every key in `range`, in key
+    /// order. The dynamically sized companion of `index_range_array_mut`.
+    ///
+    /// Time complexity: O(log n + k) expected, where k is the number of
+    /// entries in the range
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::SkipList;
+    ///
+    /// let mut skip_list = SkipList::from_sorted((0..5).map(|i| (i * 10, i)));
+    /// for value in skip_list.range_values_mut(10..30) {
+    ///     *value += 100;
+    /// }
+    ///
+    /// let values: Vec<_> = skip_list.iter().map(|(_, &v)| v).collect();
+    /// assert_eq!(values, vec![0, 101, 102, 3, 4]);
+    /// ```
+    pub fn range_values_mut<Q, R>(&mut self, range: R) -> Vec<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        let ((mut current, start_pos), (_, end_pos)) = self.range_nodes(range);
+
+        // Each node is visited once, so the references never alias
+        (start_pos..end_pos)
+            .map(|_| {
+                let mut node = current;
+                current = unsafe { node.as_ref() }.forward[0].ptr;
+                unsafe { node.as_mut() }.value_mut()
+            })
+            .collect()
+    }
+
+    /// Call `f` on every value in key order, in one pass over level 0. For
+    /// example, to release spare capacity of values that are collections:
+    ///
+    /// ```
+    /// use skiplist::SkipList;
+    ///
+    /// let mut skip_list = SkipList::new();
+    /// skip_list.insert(1, Vec::<u8>::with_capacity(1024));
+    ///
+    /// skip_list.for_each_value_mut(|value| value.shrink_to_fit());
+    /// assert_eq!(skip_list.get(&1).unwrap().capacity(), 0);
+    /// ```
+    ///
+    /// Time complexity: O(n)
+    pub fn for_each_value_mut<F: FnMut(&mut V)>(&mut self, mut f: F) {
+        let mut current = unsafe { self.head.as_ref() }.forward[0].ptr;
+        while !self.is_tail(current) {
+            let mut node = current;
+            current = unsafe { node.as_ref() }.forward[0].ptr;
+            f(unsafe { node.as_mut() }.value_mut());
+        }
+    }
+
+    /// The smallest and largest keys, or `None` if the list is empty.
+    ///
+    /// Time complexity: O(log n) expected, to reach the last node
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::SkipList;
+    ///
+    /// let skip_list = SkipList::from_sorted([(3, ()), (5, ()), (8, ())]);
+    /// assert_eq!(skip_list.key_bounds(), Some((&3, &8)));
+    /// ```
+    pub fn key_bounds(&self) -> Option<(&K, &K)> {
+        let first = self.node_at(0)?;
+        let last = self.node_at(self.len - 1)?;
+        Some(unsafe { (first.as_ref().key(), last.as_ref().key()) })
+    }
+
+    /// Get the first and last entries whose keys fall in `range`, or `None` if
+    /// the range is empty.
+    ///
+    /// The first entry is found by seeking the lower bound, the last by a
+    /// span-guided walk to the position just before the upper bound, so the
+    /// entries in between are never visited.
+    ///
+    /// Time complexity: O(log n) expected
+    #[allow(clippy::type_complexity)]
+    pub fn range_bounds_entries<Q, R>(&self, range: R) -> Option<((&K, &V), (&K, &V))>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        self.range_span(range).map(|(_, first, last)| (first, last))
+    }
+
+    /// Whether no key falls in `range`.
+    ///
+    /// Any bounds are accepted: ranges whose start is past their end, such
+    /// as `5..5`, `5..=4` or two `Excluded(5)` bounds, are simply empty
+    /// rather than a panic as in `BTreeMap::range`.
+    ///
+    /// Time complexity: O(log n) expected
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::SkipList;
+    ///
+    /// let skip_list = SkipList::from_sorted([(1, ()), (5, ())]);
+    ///
+    /// assert!(!skip_list.is_empty_range(2..=5));
+    /// assert!(skip_list.is_empty_range(2..5));
+    /// assert!(skip_list.is_empty_range(5..=4));
+    /// ```
+    pub fn is_empty_range<Q, R>(&self, range: R) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        let ((_, start_pos), (_, end_pos)) = self.range_nodes(range);
+        start_pos == end_pos
+    }
+
+    /// Get the number of entries whose keys fall in `range` together with
+    /// the first and last of them, or `None` if the range is empty: what a
+    /// scroll bar needs to size itself and label its ends.
+    ///
+    /// Both bounds are resolved by one descent each, which also yields their
+    /// positions; the last entry is then reached by a span-guided walk.
+    ///
+    /// Time complexity: O(log n) expected
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::SkipList;
+    ///
+    /// let skip_list = SkipList::from_sorted((0..10).map(|i| (i * 10, i)));
+    ///
+    /// assert_eq!(skip_list.range_span(15..=60), Some((5, (&20, &2), (&60, &6))));
+    /// assert_eq!(skip_list.range_span(61..70), None);
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub fn range_span<Q, R>(&self, range: R) -> Option<(usize, (&K, &V), (&K, &V))>
     where
         K: Borrow<Q>,
         Q: Ord + ?Sized,
+        R: RangeBounds<Q>,
     {
-        let mut cur = self.head;
-        for i in (0..=self.level).rev() {
-            loop {
-                let mut next = unsafe { cur.as_ref() }.forward[i].ptr;
-                if self.is_tail(next) {
-                    break;
-                }
-                let next_key = (unsafe { next.as_ref() }).key();
+        let ((first, start_pos), (_, end_pos)) = self.range_nodes(range);
+        if start_pos == end_pos {
+            return None;
+        }
 
-                if next_key.borrow() == key {
-                    return Some(unsafe { next.as_mut().value_mut() });
-                }
+        let first = unsafe { first.as_ref() };
+        let last = unsafe { self.node_at(end_pos - 1)?.as_ref() };
+        Some((
+            end_pos - start_pos,
+            (first.key(), first.value()),
+            (last.key(), last.value()),
+        ))
+    }
 
-                if next_key.borrow() < key {
-                    cur = next;
-                } else {
-                    break;
-                }
-            }
+    /// Cumulative sums of the values in sorted order: element `i` is the sum
+    /// of the values at positions `0..=i`.
+    ///
+    /// This is a snapshot. Any later insert, remove or value change leaves
+    /// the returned sums stale, so rebuild them after mutating the list.
+    /// Pair with `range_sum_via` to answer range sums in O(1).
+    ///
+    /// Time complexity: O(n)
+    pub fn build_prefix_sums(&self) -> Vec<V>
+    where
+        V: Clone + Add<Output = V>,
+    {
+        let mut sums: Vec<V> = Vec::with_capacity(self.len);
+        for (_, value) in self.iter() {
+            let sum = match sums.last() {
+                Some(total) => total.clone() + value.clone(),
+                None => value.clone(),
+            };
+            sums.push(sum);
         }
-
-        None
+        sums
     }
 
-    /// Get the key-value pair at the specified index using span information for efficient traversal.
-    /// Returns None if the index is out of bounds.
+    /// Sum of the values at positions `lo..hi`, read off sums built by
+    /// `build_prefix_sums`. `hi` is clamped to the number of sums; returns
+    /// `None` if the range is empty.
     ///
-    /// Time complexity: O(log n) expected
+    /// Time complexity: O(1)
     ///
     /// # Examples
     ///
     /// ```
     /// use skiplist::SkipList;
     ///
-    /// let mut skip_list = SkipList::new();
-    /// skip_list.insert(1, "first");
-    /// skip_list.insert(2, "second");
-    /// skip_list.insert(3, "third");
+    /// let skip_list = SkipList::from_sorted((0..10).map(|i| (i, i)));
+    /// let prefix = skip_list.build_prefix_sums();
     ///
-    /// assert_eq!(skip_list.index(0), Some((&1, &"first")));
-    /// assert_eq!(skip_list.index(1), Some((&2, &"second")));
-    /// assert_eq!(skip_list.index(2), Some((&3, &"third")));
-    /// assert_eq!(skip_list.index(3), None);
+    /// assert_eq!(SkipList::<i32, i32>::range_sum_via(&prefix, 2, 5), Some(2 + 3 + 4));
+    /// assert_eq!(SkipList::<i32, i32>::range_sum_via(&prefix, 5, 5), None);
     /// ```
-    pub fn index(&self, index: usize) -> Option<(&K, &V)> {
+    pub fn range_sum_via(prefix: &[V], lo: usize, hi: usize) -> Option<V>
+    where
+        V: Clone + Sub<Output = V>,
+    {
+        let hi = hi.min(prefix.len());
+        if lo >= hi {
+            return None;
+        }
+
+        let upper = prefix[hi - 1].clone();
+        match lo {
+            0 => Some(upper),
+            _ => Some(upper - prefix[lo - 1].clone()),
+        }
+    }
+
+    /// Locate the node at the specified index by following spans from the
+    /// highest level down.
+    fn node_at(&self, index: usize) -> Option<NodePtr<K, V>> {
         if index >= self.len {
             return None;
         }
@@ -475,115 +2739,284 @@ impl<K: Key, V: Value> SkipList<K, V> {
 
         // If we found the exact index and it's not the head or tail
         if current_index == target_index && !self.is_head(current) && !self.is_tail(current) {
-            let node = unsafe { current.as_ref() };
-            Some((node.key(), node.value()))
+            Some(current)
         } else {
             None
         }
     }
 
-    /// Get a mutable reference to the value at the specified index.
-    /// Returns None if the index is out of bounds.
+    fn rand_level(&mut self) -> usize {
+        self.levels.random_level(self.len)
+    }
+}
+
+impl<V: Value> SkipList<u64, V> {
+    /// Smallest integer in `range` that is not a key, or `None` if every
+    /// integer in `range` is present.
+    ///
+    /// Keys are distinct integers, so `key - rank` never decreases along the
+    /// list, and the keys from `range.start` on are gap-free exactly while
+    /// it stays equal. A single span-guided descent finds the last node of
+    /// that dense run, whatever its length.
     ///
     /// Time complexity: O(log n) expected
-    pub fn index_mut(&mut self, index: usize) -> Option<(&K, &mut V)> {
-        if index >= self.len {
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::SkipList;
+    ///
+    /// let ids = SkipList::from_sorted([(0u64, ()), (1, ()), (2, ()), (4, ())]);
+    ///
+    /// assert_eq!(ids.first_missing_in(0..10), Some(3));
+    /// assert_eq!(ids.first_missing_in(4..10), Some(5));
+    /// assert_eq!(ids.first_missing_in(0..3), None);
+    /// ```
+    pub fn first_missing_in(&self, range: Range<u64>) -> Option<u64> {
+        if range.is_empty() {
             return None;
         }
 
-        let target_index = index + 1; // +1 because head is at position 0
-        let mut current = self.head;
-        let mut current_index = 0;
-
-        // Traverse from highest level to lowest, using spans to skip efficiently
-        for level in (0..=self.level).rev() {
-            while current_index < target_index {
-                let current_node = unsafe { current.as_ref() };
-
-                // Check if this level has a forward pointer
-                if level < current_node.forward.len() {
-                    let forward_ptr = current_node.forward[level];
-                    let next_index = current_index + forward_ptr.span;
-
-                    // If the next span would overshoot our target, move to lower level
-                    if next_index > target_index {
-                        break;
-                    }
-
-                    // If we reach the tail, we've gone too far
-                    if self.is_tail(forward_ptr.ptr) {
-                        break;
-                    }
+        // Position (head is 0) the first key >= `range.start` has, or would have
+        let first = self.count_lt(&range.start) as u128 + 1;
+        let start = range.start as u128;
 
-                    current = forward_ptr.ptr;
-                    current_index = next_index;
-                } else {
+        // Advance over every node with `key - position <= start - first`,
+        // which covers all smaller keys plus the dense run from `start`
+        let mut position = 0;
+        let mut cur = self.head;
+        for i in (0..=self.level).rev() {
+            loop {
+                let forward_ptr = unsafe { cur.as_ref() }.forward[i];
+                if self.is_tail(forward_ptr.ptr) {
+                    break;
+                }
+                let key = *unsafe { forward_ptr.ptr.as_ref() }.key() as u128;
+                let next_position = (position + forward_ptr.span) as u128;
+                if key + first > start + next_position {
                     break;
                 }
+                position += forward_ptr.span;
+                cur = forward_ptr.ptr;
             }
         }
 
-        // If we found the exact index and it's not the head or tail
-        if current_index == target_index && !self.is_head(current) && !self.is_tail(current) {
-            let node = unsafe { current.as_mut() };
-            let key_ref = unsafe { current.as_ref() }.key(); // Get immutable reference to key
-            let value_ref = node.value_mut(); // Get mutable reference to value
-            Some((key_ref, value_ref))
-        } else {
-            None
-        }
+        let dense = (position as u128 + 1).saturating_sub(first);
+        let missing = start + dense;
+        (missing < range.end as u128).then_some(missing as u64)
+    }
+}
+
+/// Clones the exact structure: every node keeps its level, so the copy has
+/// the same tower and spans as the original. A custom RNG supplied through
+/// the builder is not cloned; the copy draws future levels from the
+/// thread-local RNG with the same probability and max level. A node pool is
+/// shared with the copy.
+impl<K: Key + Clone, V: Value + Clone> Clone for SkipList<K, V> {
+    fn clone(&self) -> Self {
+        let mut current = unsafe { self.head.as_ref() }.forward[0].ptr;
+        let entries = std::iter::from_fn(|| {
+            if self.is_tail(current) {
+                return None;
+            }
+            let node = unsafe { current.as_ref() };
+            current = node.forward[0].ptr;
+            Some((node.key().clone(), node.value().clone(), node.level))
+        });
+
+        let mut list = Self::from_sorted_with_levels(entries);
+        list.inherit_config(self);
+        list
+    }
+}
+
+impl<K: Key, V: Value> Default for SkipList<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Builds the list in O(n) with `from_sorted`, as the map is already ordered.
+impl<K: Key, V: Value> From<BTreeMap<K, V>> for SkipList<K, V> {
+    fn from(map: BTreeMap<K, V>) -> Self {
+        Self::from_sorted(map)
+    }
+}
+
+impl<K: Key, V: Value> From<SkipList<K, V>> for BTreeMap<K, V> {
+    fn from(skip_list: SkipList<K, V>) -> Self {
+        skip_list.into_iter().collect()
+    }
+}
+
+/// Inserts every entry, later ones replacing the values of earlier equal
+/// keys. See `extend_counted` to learn how many keys were new.
+impl<K: Key, V: Value> Extend<(K, V)> for SkipList<K, V> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        self.extend_counted(iter);
     }
+}
 
-    fn rand_level() -> usize {
-        let mut level = 0;
+/// Collects into a list with the default configuration; see `from_iter_with`
+/// to choose another one, or `from_sorted` for already sorted input.
+impl<K: Key, V: Value> FromIterator<(K, V)> for SkipList<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut skip_list = Self::new();
+        skip_list.extend(iter);
+        skip_list
+    }
+}
 
-        while rand::random::<f64>() < 0.5 && level < MAX_LEVEL {
-            level += 1;
+impl<K: Key, V: Value> SkipList<K, V> {
+    /// Unlink the first node from level 0 only and hand back its entry.
+    ///
+    /// Only for tearing the list down: the upper levels keep pointing at the
+    /// freed node, so afterwards nothing but level 0 may be followed, which
+    /// is all `drop` does. The node is unlinked before its entry is returned,
+    /// so if dropping the entry panics the node is neither freed twice nor
+    /// visited again.
+    pub(crate) fn take_first_for_teardown(&mut self) -> Option<(K, V)> {
+        let first = unsafe { self.head.as_ref() }.forward[0].ptr;
+        if self.is_tail(first) {
+            return None;
         }
 
-        level
+        unsafe { self.head.as_mut() }.forward[0].ptr = unsafe { first.as_ref() }.forward[0].ptr;
+        self.len -= 1;
+        Some(self.free_node(first))
     }
 }
 
+/// Frees every node even if dropping a key or value panics: the guard keeps
+/// going from the next node while unwinding. A second panic while unwinding
+/// aborts, as it would for any other collection.
 impl<K: Key, V: Value> Drop for SkipList<K, V> {
     fn drop(&mut self) {
-        unsafe {
-            let mut cur = self.head.as_ref().forward[0].ptr;
+        struct Guard<'a, K: Key, V: Value>(&'a mut SkipList<K, V>);
 
-            while !self.is_tail(cur) {
-                let next = cur.as_ref().forward[0].ptr;
-                let _ = Box::from_raw(cur.as_ptr());
-                cur = next;
-            }
+        impl<K: Key, V: Value> Drop for Guard<'_, K, V> {
+            fn drop(&mut self) {
+                while self.0.take_first_for_teardown().is_some() {}
 
-            let _ = Box::from_raw(self.head.as_ptr());
-            let _ = Box::from_raw(self.tail.as_ptr());
+                unsafe {
+                    let _ = Box::from_raw(self.0.head.as_ptr());
+                    let _ = Box::from_raw(self.0.tail.as_ptr());
+                }
+            }
         }
+
+        let guard = Guard(self);
+        while guard.0.take_first_for_teardown().is_some() {}
     }
 }
 
+/// Formats the entries in sorted order as `{k: v, ...}`, like a map's
+/// `Debug` output. Use `format_tower` for the diagram of every level.
 impl<K: Key + fmt::Debug, V: Value + fmt::Debug> fmt::Display for SkipList<K, V> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        // 1. Get all nodes from level 0. This defines the columns of our graph.
-        let mut nodes_l0 = vec![];
-        let mut current = self.head;
-        loop {
-            nodes_l0.push(current);
-            if self.is_tail(current) {
-                break;
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+/// Displays a list as its tower diagram, see `SkipList::format_tower`.
+struct Tower<'a, K: Key, V: Value>(&'a SkipList<K, V>);
+
+impl<K: Key + fmt::Debug, V: Value + fmt::Debug> fmt::Display for Tower<'_, K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt_tower(f)
+    }
+}
+
+impl<K: Key + fmt::Debug, V: Value + fmt::Debug> SkipList<K, V> {
+    /// Render the entries in sorted order as `{k: v, ...}`. Same as the
+    /// `Display` output.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::SkipList;
+    ///
+    /// let skip_list = SkipList::from_sorted([(1, "one"), (2, "two")]);
+    /// assert_eq!(skip_list.format_compact(), r#"{1: "one", 2: "two"}"#);
+    /// ```
+    pub fn format_compact(&self) -> String {
+        self.to_string()
+    }
+
+    /// Render only the first `n` and the last `n` entries like
+    /// `format_compact`, with `...` standing for everything in between, so
+    /// that huge lists stay readable. Lists of at most `2 * n` entries are
+    /// rendered in full.
+    ///
+    /// Time complexity: O(log len + n) expected
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::SkipList;
+    ///
+    /// let skip_list = SkipList::from_sorted((1..=100).map(|i| (i, i * 10)));
+    /// assert_eq!(skip_list.debug_head_tail(2), "{1: 10, 2: 20, ..., 99: 990, 100: 1000}");
+    /// ```
+    pub fn debug_head_tail(&self, n: usize) -> String {
+        if self.len <= n.saturating_mul(2) {
+            return self.format_compact();
+        }
+
+        let entry = |(key, value): (&K, &V)| format!("{:?}: {:?}", key, value);
+        let parts: Vec<_> = self
+            .iter()
+            .take(n)
+            .map(entry)
+            .chain(std::iter::once("...".to_string()))
+            .chain(self.position_range(self.len - n..).map(entry))
+            .collect();
+
+        format!("{{{}}}", parts.join(", "))
+    }
+
+    /// Render every level as an ASCII diagram, with each node's outgoing span
+    /// printed under it. Meant for debugging the structure itself.
+    ///
+    /// Every node takes a column on every level, so for lists longer than
+    /// 64 entries only the first and last 8 columns are drawn, around a
+    /// `...` column, to keep an accidental `println!` of a big list short.
+    pub fn format_tower(&self) -> String {
+        Tower(self).to_string()
+    }
+
+    fn fmt_tower(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // 1. Pick the columns of the graph: the head, the level 0 nodes and
+        // the tail, with the middle of a long list elided (`None`).
+        let mut columns = vec![Some(self.head)];
+        if self.len > TOWER_MAX_NODES {
+            let edge = |start: NodePtr<K, V>| {
+                std::iter::successors(Some(start), |&node| {
+                    Some(unsafe { node.as_ref() }.forward[0].ptr)
+                })
+                .take(TOWER_EDGE_NODES)
+                .map(Some)
+            };
+            columns.extend(edge(unsafe { self.head.as_ref() }.forward[0].ptr));
+            columns.push(None);
+            columns.extend(edge(self.node_at(self.len - TOWER_EDGE_NODES).unwrap()));
+        } else {
+            let mut current = unsafe { self.head.as_ref() }.forward[0].ptr;
+            while !self.is_tail(current) {
+                columns.push(Some(current));
+                current = unsafe { current.as_ref() }.forward[0].ptr;
             }
-            current = unsafe { current.as_ref() }.forward[0].ptr;
         }
+        columns.push(Some(self.tail));
 
-        // 2. Get string representations for each node.
-        let node_reprs: Vec<String> = nodes_l0
+        // 2. Get string representations for each column.
+        let reprs: Vec<String> = columns
             .iter()
-            .map(|&node_ptr| {
-                if self.is_head(node_ptr) {
-                    "HEAD".to_string()
-                } else if self.is_tail(node_ptr) {
-                    "TAIL".to_string()
-                } else {
+            .map(|&column| match column {
+                None => "...".to_string(),
+                Some(node_ptr) if self.is_head(node_ptr) => "HEAD".to_string(),
+                Some(node_ptr) if self.is_tail(node_ptr) => "TAIL".to_string(),
+                Some(node_ptr) => {
                     format!("({:?}: {:?})", unsafe { node_ptr.as_ref().key() }, unsafe {
                         node_ptr.as_ref().value()
                     })
@@ -591,28 +3024,32 @@ impl<K: Key + fmt::Debug, V: Value + fmt::Debug> fmt::Display for SkipList<K, V>
             })
             .collect();
 
+        // The node of a column if it has a tower on level `i`
+        let on_level = |column: Option<NodePtr<K, V>>, i: usize| {
+            column.filter(|&node_ptr| {
+                self.is_head(node_ptr)
+                    || self.is_tail(node_ptr)
+                    || unsafe { node_ptr.as_ref() }.level >= i
+            })
+        };
+
         // 3. Print each level from top to bottom.
         for i in (0..=self.level).rev() {
             // Print node line
             write!(f, "L{:<2}|", i)?;
-            let mut node_on_level = self.head;
-            for (l0_idx, &l0_node) in nodes_l0.iter().enumerate() {
-                let repr = &node_reprs[l0_idx];
-                let is_node_on_level = l0_node == node_on_level;
-
-                if l0_idx > 0 {
-                    if is_node_on_level {
+            for (idx, (&column, repr)) in columns.iter().zip(&reprs).enumerate() {
+                if idx > 0 {
+                    if column.is_none() {
+                        write!(f, "    ")?;
+                    } else if on_level(column, i).is_some() {
                         write!(f, " -> ")?;
                     } else {
                         write!(f, "----")?;
                     }
                 }
 
-                if is_node_on_level {
+                if column.is_none() || on_level(column, i).is_some() {
                     write!(f, "{}", repr)?;
-                    if !self.is_tail(node_on_level) {
-                        node_on_level = unsafe { node_on_level.as_ref() }.forward[i].ptr;
-                    }
                 } else {
                     write!(f, "{}", "-".repeat(repr.len()))?;
                 }
@@ -621,26 +3058,19 @@ impl<K: Key + fmt::Debug, V: Value + fmt::Debug> fmt::Display for SkipList<K, V>
 
             // Print spans line
             write!(f, "   |")?;
-            let mut node_on_level_for_span = self.head;
-            for (l0_idx, &l0_node) in nodes_l0.iter().enumerate() {
-                let repr = &node_reprs[l0_idx];
-                if l0_idx > 0 {
+            for (idx, (&column, repr)) in columns.iter().zip(&reprs).enumerate() {
+                if idx > 0 {
                     write!(f, "    ")?;
                 }
 
-                if l0_node == node_on_level_for_span {
-                    if !self.is_tail(node_on_level_for_span) {
-                        let forward_ptr = unsafe { node_on_level_for_span.as_ref() }.forward[i];
-                        let span = forward_ptr.span;
-                        let span_str = format!("({})", span);
+                match on_level(column, i) {
+                    Some(node_ptr) if !self.is_tail(node_ptr) => {
+                        let span_str =
+                            format!("({})", unsafe { node_ptr.as_ref() }.forward[i].span);
                         write!(f, "{:<width$}", span_str, width = repr.len())?;
-                        node_on_level_for_span = forward_ptr.ptr;
-                    } else {
-                        // TAIL node has no outgoing span
-                        write!(f, "{}", " ".repeat(repr.len()))?;
                     }
-                } else {
-                    write!(f, "{}", " ".repeat(repr.len()))?;
+                    // TAIL node has no outgoing span
+                    _ => write!(f, "{}", " ".repeat(repr.len()))?,
                 }
             }
             writeln!(f)?;
@@ -648,20 +3078,14 @@ impl<K: Key + fmt::Debug, V: Value + fmt::Debug> fmt::Display for SkipList<K, V>
             // 4. Print vertical connectors.
             if i > 0 {
                 write!(f, "   |")?;
-                let mut node_on_level_for_vertical = self.head;
-                for (l0_idx, &l0_node) in nodes_l0.iter().enumerate() {
-                    let repr = &node_reprs[l0_idx];
-                    if l0_idx > 0 {
+                for (idx, (&column, repr)) in columns.iter().zip(&reprs).enumerate() {
+                    if idx > 0 {
                         write!(f, "    ")?;
                     }
 
-                    if l0_node == node_on_level_for_vertical {
+                    if on_level(column, i).is_some() {
                         write!(f, "|")?;
                         write!(f, "{}", " ".repeat(repr.len() - 1))?;
-                        if !self.is_tail(node_on_level_for_vertical) {
-                            node_on_level_for_vertical =
-                                unsafe { node_on_level_for_vertical.as_ref() }.forward[i].ptr;
-                        }
                     } else {
                         write!(f, "{}", " ".repeat(repr.len()))?;
                     }
@@ -677,6 +3101,41 @@ impl<K: Key + fmt::Debug, V: Value + fmt::Debug> fmt::Display for SkipList<K, V>
 mod tests {
     use super::*;
 
+    /// Fraction of `samples` levels drawn at each level, up to level 9.
+    fn level_histogram(draw: impl Fn(&mut rand::rngs::StdRng) -> usize, seed: u64) -> Vec<f64> {
+        use rand::SeedableRng;
+
+        let samples = 50_000;
+        let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+        let mut counts = [0usize; 10];
+        for _ in 0..samples {
+            counts[draw(&mut rng).min(9)] += 1;
+        }
+        counts.iter().map(|&c| c as f64 / samples as f64).collect()
+    }
+
+    #[test]
+    fn test_draw_level_distribution() {
+        let fast = level_histogram(|rng| draw_level(rng, 0.5, MAX_LEVEL), 1);
+        let coin = level_histogram(|rng| coin_flip_level(rng, 0.5, MAX_LEVEL), 2);
+
+        for level in 0..9 {
+            let expected = 0.5f64.powi(level as i32 + 1);
+            assert!(
+                (fast[level] - expected).abs() < 0.01,
+                "level {}: {}",
+                level,
+                fast[level]
+            );
+            assert!((fast[level] - coin[level]).abs() < 0.01, "level {}", level);
+        }
+
+        // The cap still applies
+        let capped = level_histogram(|rng| draw_level(rng, 0.5, 2), 3);
+        assert!((capped[2] - 0.25).abs() < 0.01);
+        assert!(capped[3..].iter().all(|&f| f == 0.0));
+    }
+
     #[test]
     fn test_basic_skiplist_operations() {
         let mut skip_list = SkipList::new();
@@ -877,4 +3336,193 @@ mod tests {
             assert_eq!(skip_list.index(idx), Some((&expected_key, &expected_value)));
         }
     }
+
+    #[test]
+    fn test_from_sorted() {
+        let skip_list = SkipList::from_sorted((0..100).map(|i| (i, i * 10)));
+
+        assert_eq!(skip_list.len(), 100);
+        assert!(skip_list.verify_spans());
+        for i in 0..100 {
+            assert_eq!(skip_list.get(&i), Some(&(i * 10)));
+            assert_eq!(skip_list.index(i as usize), Some((&i, &(i * 10))));
+        }
+
+        let empty: SkipList<i32, i32> = SkipList::from_sorted(vec![]);
+        assert!(empty.is_empty());
+        assert!(empty.verify_spans());
+    }
+
+    #[cfg(feature = "test-utils")]
+    #[test]
+    fn test_validate_and_repair() {
+        let mut skip_list = SkipList::from_sorted((0..50).map(|i| (i, i * 10)));
+
+        // Nothing to repair on a healthy list
+        assert!(!skip_list.validate_and_repair());
+
+        // Corrupt the head's span on level 0
+        unsafe { skip_list.head.as_mut() }.forward[0].span = 7;
+        assert!(!skip_list.verify_spans());
+
+        assert!(skip_list.validate_and_repair());
+        assert!(skip_list.verify_spans());
+        assert_eq!(skip_list.len(), 50);
+
+        let items: Vec<_> = skip_list.iter().map(|(&k, &v)| (k, v)).collect();
+        let expected: Vec<_> = (0..50).map(|i| (i, i * 10)).collect();
+        assert_eq!(items, expected);
+    }
+
+    #[cfg(feature = "test-utils")]
+    #[test]
+    fn test_validate_and_repair_keeps_config() {
+        use rand::SeedableRng;
+
+        let mut skip_list = crate::SkipListBuilder::new()
+            .probability(0.25)
+            .max_level(3)
+            .rng(rand::rngs::StdRng::seed_from_u64(1))
+            .build()
+            .unwrap();
+        skip_list.set_auto_shrink(true);
+        for i in 0..50 {
+            skip_list.insert(i, i);
+        }
+
+        unsafe { skip_list.head.as_mut() }.forward[0].span = 7;
+        assert!(skip_list.validate_and_repair());
+        assert!(skip_list.verify_spans());
+
+        // The rebuilt tower was drawn with the list's own settings
+        assert_eq!(skip_list.levels.probability, 0.25);
+        assert_eq!(skip_list.levels.max_level, 3);
+        assert!(skip_list.levels.rng.is_some());
+        assert!(skip_list.level <= 3);
+        assert!(skip_list.auto_shrink);
+    }
+
+    #[test]
+    fn test_insert_with_level() {
+        let mut skip_list = SkipList::new();
+
+        // L1: HEAD ------> 2 ------> 4 -> TAIL
+        // L0: HEAD -> 1 -> 2 -> 3 -> 4 -> TAIL
+        skip_list.insert_with_level(3, "three", 0);
+        skip_list.insert_with_level(1, "one", 0);
+        skip_list.insert_with_level(4, "four", 1);
+        skip_list.insert_with_level(2, "two", 1);
+
+        assert_eq!(skip_list.level, 1);
+        assert_eq!(
+            skip_list.dump_spans(),
+            vec![vec![1, 1, 1, 1, 1], vec![2, 2, 1]]
+        );
+        assert!(skip_list.verify_spans());
+
+        // Replacing keeps the original tower
+        assert_eq!(skip_list.insert_with_level(2, "TWO", 0), Some("two"));
+        assert_eq!(
+            skip_list.dump_spans(),
+            vec![vec![1, 1, 1, 1, 1], vec![2, 2, 1]]
+        );
+
+        for (i, key) in [1, 2, 3, 4].iter().enumerate() {
+            assert_eq!(skip_list.index(i).map(|(k, _)| k), Some(key));
+        }
+    }
+
+    #[test]
+    fn test_from_sorted_dedup() {
+        let skip_list = SkipList::from_sorted_dedup(vec![(1, "a"), (1, "b"), (2, "c")]);
+
+        assert_eq!(skip_list.len(), 2);
+        assert!(skip_list.verify_spans());
+        assert_eq!(skip_list.get(&1), Some(&"b"));
+        assert_eq!(skip_list.get(&2), Some(&"c"));
+        assert_eq!(skip_list.index(0), Some((&1, &"b")));
+        assert_eq!(skip_list.index(1), Some((&2, &"c")));
+
+        let skip_list =
+            SkipList::from_sorted_dedup((0..300).flat_map(|i| [(i / 3, i), (i / 3, i + 1)]));
+        assert_eq!(skip_list.len(), 100);
+        assert!(skip_list.verify_spans());
+        for i in 0..100 {
+            assert_eq!(skip_list.get(&i), Some(&(i * 3 + 3)));
+        }
+    }
+
+    #[test]
+    fn test_span_helpers() {
+        // A link from position 3 spanning 5 reaches position 8, which moves
+        // up to 9 when a node is inserted at 4, 6 or 8
+        assert_eq!(split_span(3, 5, 4), (1, 5));
+        assert_eq!(split_span(3, 5, 6), (3, 3));
+        assert_eq!(split_span(3, 5, 8), (5, 1));
+        assert_eq!(
+            split_span(0, usize::MAX - 1, usize::MAX - 1),
+            (usize::MAX - 1, 1)
+        );
+
+        assert_eq!(join_spans(1, 1), 1);
+        assert_eq!(join_spans(3, 5), 7);
+        assert_eq!(join_spans(usize::MAX, 1), usize::MAX);
+
+        assert_eq!(shrink_span(2), 1);
+        assert_eq!(shrink_span(usize::MAX), usize::MAX - 1);
+    }
+
+    // The guards are debug assertions, compiled out in release builds
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "outside the link")]
+    fn test_split_span_rejects_position_past_link() {
+        split_span(3, 5, 9);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "cannot pass over a removed node")]
+    fn test_shrink_span_rejects_adjacent_link() {
+        shrink_span(1);
+    }
+
+    #[test]
+    fn test_span_arithmetic_with_long_links() {
+        // L2: HEAD -> 0 --------------------------------> 2n+2 -> TAIL
+        // L1: HEAD -> 0 --------------------------------> 2n+2 -> TAIL
+        // L0: HEAD -> 0 -> 2 -> 4 -> ... -> 2n-2 -> 2n -> 2n+2 -> TAIL
+        let n = 100_000;
+        let mut skip_list = SkipList::from_sorted_with_levels(
+            (0..=n + 1).map(|i| (2 * i, i, if i == 0 || i == n + 1 { 2 } else { 0 })),
+        );
+        let original = skip_list.dump_spans();
+        assert_eq!(original[1], vec![1, n + 1, 1]);
+
+        // A level 1 node in the middle splits the long level 1 and 2 links
+        // exactly where the level 0 walk ends
+        skip_list.insert_with_level(n + 1, 0, 1);
+        assert_eq!(skip_list.dump_spans()[1], vec![1, n / 2 + 1, n / 2 + 1, 1]);
+        assert_eq!(skip_list.dump_spans()[2], vec![1, n + 2, 1]);
+        assert!(skip_list.verify_spans());
+
+        // A new top level starts with links covering the whole list
+        skip_list.insert_with_level(2 * n + 3, 0, 4);
+        assert_eq!(skip_list.dump_spans()[4], vec![n + 4, 1]);
+        assert!(skip_list.verify_spans());
+
+        // Removing both joins the split links back and shrinks the ones
+        // passing over them, restoring every span
+        skip_list.remove(&(2 * n + 3));
+        skip_list.remove(&(n + 1));
+        assert_eq!(skip_list.dump_spans(), original);
+
+        // Removing an end of a long link joins it with its neighbour
+        skip_list.remove(&0);
+        assert_eq!(skip_list.dump_spans()[2], vec![n + 1, 1]);
+        skip_list.remove(&(2 * n + 2));
+        assert_eq!(skip_list.dump_spans().len(), 1);
+        assert!(skip_list.verify_spans());
+        assert_eq!(skip_list.len(), n);
+    }
 }
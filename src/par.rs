@@ -0,0 +1,32 @@
+use rayon::iter::IntoParallelIterator;
+
+use crate::{Key, SkipList, Value};
+
+/// Consumes the list into a parallel iterator over its entries, in sorted
+/// order for order-preserving adaptors like `collect`.
+///
+/// A linked list cannot be split without walking it, so the entries are
+/// first moved out into a `Vec` in one sequential O(n) pass, which rayon then
+/// splits freely. Every node is freed during that pass, exactly once and on
+/// the calling thread, since a node pool is not thread-safe; the parallel
+/// part only ever sees owned keys and values.
+///
+/// # Examples
+///
+/// ```
+/// use rayon::prelude::*;
+/// use skiplist::SkipList;
+///
+/// let skip_list = SkipList::from_sorted((0..1000u64).map(|i| (i, i * 2)));
+/// let total: u64 = skip_list.into_par_iter().map(|(_, v)| v).sum();
+///
+/// assert_eq!(total, 999_000);
+/// ```
+impl<K: Key + Send, V: Value + Send> IntoParallelIterator for SkipList<K, V> {
+    type Iter = rayon::vec::IntoIter<(K, V)>;
+    type Item = (K, V);
+
+    fn into_par_iter(self) -> Self::Iter {
+        self.into_sorted_vec().into_par_iter()
+    }
+}
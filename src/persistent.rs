@@ -0,0 +1,376 @@
+use std::{borrow::Borrow, fmt, slice, sync::Arc};
+
+use crate::{DEFAULT_PROBABILITY, Key, MAX_LEVEL, Value, draw_level};
+
+/// An immutable skip list whose versions share structure, so that taking a
+/// snapshot is an O(1) clone.
+///
+/// `insert` and `remove` leave `self` untouched and return a new version.
+/// Only the towers on the search path are copied; everything else, including
+/// the entries themselves, is shared between versions through `Arc`s.
+///
+/// A pointer-linked skip list cannot be path-copied, since a node is reached
+/// from a different predecessor on every level. Here the levels are nested
+/// instead: the level `i` tower of an entry owns the level `i - 1` towers of
+/// every entry up to the next one that reaches level `i`, the same run of
+/// nodes that a level `i` forward pointer spans.
+///
+/// # Examples
+///
+/// ```
+/// use skiplist::PersistentSkipList;
+///
+/// let empty = PersistentSkipList::new();
+/// let one = empty.insert(1, "one");
+/// let two = one.insert(2, "two");
+///
+/// assert_eq!(two.get(&1), Some(&"one"));
+/// assert_eq!(one.get(&2), None);
+/// assert!(empty.is_empty());
+/// ```
+pub struct PersistentSkipList<K, V> {
+    /// The head's tower on the level above the highest entry level, so that
+    /// every top-level tower is one of its children.
+    root: TowerPtr<K, V>,
+    /// Level of `root`, always at least 1.
+    level: usize,
+    len: usize,
+}
+
+type TowerPtr<K, V> = Arc<Tower<K, V>>;
+
+/// One level of an entry's tower (or of the head's, when `entry` is `None`).
+///
+/// The first child is the level below of the same tower; level 0 towers have
+/// no children.
+struct Tower<K, V> {
+    entry: Option<Arc<(K, V)>>,
+    children: Vec<TowerPtr<K, V>>,
+}
+
+impl<K: Key, V: Value> Tower<K, V> {
+    fn new(entry: Option<Arc<(K, V)>>, children: Vec<Arc<Self>>) -> Arc<Self> {
+        Arc::new(Self { entry, children })
+    }
+
+    fn key(&self) -> Option<&K> {
+        self.entry.as_ref().map(|entry| &entry.0)
+    }
+
+    fn has_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.key().is_some_and(|k| k.borrow() == key)
+    }
+
+    /// Index of the last child whose key is not greater than `key`. The first
+    /// child always qualifies, its key being this tower's own.
+    fn child_for<Q>(&self, key: &Q) -> usize
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.children
+            .partition_point(|child| child.key().is_none_or(|k| k.borrow() <= key))
+            - 1
+    }
+
+    /// Copy of this tower with `children[index]` swapped for `replacement`.
+    fn with_child(&self, index: usize, replacement: Arc<Self>) -> Arc<Self> {
+        let mut children = self.children.clone();
+        children[index] = replacement;
+        Self::new(self.entry.clone(), children)
+    }
+}
+
+impl<K: Key, V: Value> PersistentSkipList<K, V> {
+    pub fn new() -> Self {
+        let head = Tower::new(None, Vec::new());
+
+        Self {
+            root: Tower::new(None, vec![head]),
+            level: 1,
+            len: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Time complexity: O(log n) expected
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let mut tower = &self.root;
+        for _ in 0..self.level {
+            tower = &tower.children[tower.child_for(key)];
+            // Stop as soon as the key's tower is reached, on whatever level
+            if tower.has_key(key) {
+                return tower.entry.as_ref().map(|entry| &entry.1);
+            }
+        }
+
+        None
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.get(key).is_some()
+    }
+
+    /// Return a new version with `key` mapped to `value`, replacing any
+    /// previous value. `self` is unchanged.
+    ///
+    /// Time complexity: O(log n) expected
+    pub fn insert(&self, key: K, value: V) -> Self {
+        let entry = Arc::new((key, value));
+
+        // An existing key keeps its tower; only the entry is swapped
+        if let Some(root) = Self::replace_in(&self.root, self.level, &entry) {
+            return Self {
+                root,
+                level: self.level,
+                len: self.len,
+            };
+        }
+
+        let entry_level = draw_level(&mut rand::rng(), DEFAULT_PROBABILITY, MAX_LEVEL);
+
+        // Raise the head until the new tower fits below it
+        let mut root = Arc::clone(&self.root);
+        let mut level = self.level;
+        while level <= entry_level {
+            root = Tower::new(None, vec![root]);
+            level += 1;
+        }
+
+        let (root, _) = Self::insert_in(&root, level, &entry, entry_level);
+        Self {
+            root,
+            level,
+            len: self.len + 1,
+        }
+    }
+
+    /// Copy of `tower` (on `level`) with the tower of `entry`'s key pointing
+    /// at `entry` instead, or `None` if the key is absent.
+    fn replace_in(
+        tower: &Tower<K, V>,
+        level: usize,
+        entry: &Arc<(K, V)>,
+    ) -> Option<TowerPtr<K, V>> {
+        let index = tower.child_for(&entry.0);
+        let child = &tower.children[index];
+
+        let replacement = if child.has_key(&entry.0) {
+            Self::relabel(child, level - 1, entry)
+        } else if level == 1 {
+            return None;
+        } else {
+            Self::replace_in(child, level - 1, entry)?
+        };
+
+        Some(tower.with_child(index, replacement))
+    }
+
+    /// Copy of the tower from `level` down to 0 with every level holding
+    /// `entry`.
+    fn relabel(tower: &Tower<K, V>, level: usize, entry: &Arc<(K, V)>) -> TowerPtr<K, V> {
+        let mut children = tower.children.clone();
+        if level > 0 {
+            children[0] = Self::relabel(&children[0], level - 1, entry);
+        }
+
+        Tower::new(Some(Arc::clone(entry)), children)
+    }
+
+    /// Insert the absent `entry`, whose tower reaches `entry_level`, below
+    /// `tower` (on `level`).
+    ///
+    /// Returns the copy of `tower` and, if the new tower reaches this level
+    /// too, the new entry's tower on this level, which takes over the
+    /// children after it. The caller adds it as the next sibling.
+    fn insert_in(
+        tower: &Tower<K, V>,
+        level: usize,
+        entry: &Arc<(K, V)>,
+        entry_level: usize,
+    ) -> (TowerPtr<K, V>, Option<TowerPtr<K, V>>) {
+        let index = tower.child_for(&entry.0);
+        let (child, split) = if level == 1 {
+            let leaf = Tower::new(Some(Arc::clone(entry)), Vec::new());
+            (Arc::clone(&tower.children[index]), Some(leaf))
+        } else {
+            Self::insert_in(&tower.children[index], level - 1, entry, entry_level)
+        };
+
+        let mut children = tower.children[..index].to_vec();
+        children.push(child);
+        let rest = &tower.children[index + 1..];
+
+        match split {
+            Some(split) if entry_level >= level => {
+                let mut split_children = vec![split];
+                split_children.extend_from_slice(rest);
+                (
+                    Tower::new(tower.entry.clone(), children),
+                    Some(Tower::new(Some(Arc::clone(entry)), split_children)),
+                )
+            }
+            split => {
+                children.extend(split);
+                children.extend_from_slice(rest);
+                (Tower::new(tower.entry.clone(), children), None)
+            }
+        }
+    }
+
+    /// Return a new version without `key`, or an unchanged copy if it is
+    /// absent. `self` is unchanged.
+    ///
+    /// Time complexity: O(log n) expected
+    pub fn remove<Q>(&self, key: &Q) -> Self
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let Some(mut root) = Self::remove_in(&self.root, self.level, key) else {
+            return self.clone();
+        };
+
+        // Lower the head while it has no other tower on the level below
+        let mut level = self.level;
+        while level > 1 && root.children.len() == 1 {
+            root = Arc::clone(&root.children[0]);
+            level -= 1;
+        }
+
+        Self {
+            root,
+            level,
+            len: self.len - 1,
+        }
+    }
+
+    /// Copy of `tower` (on `level`) without `key`, or `None` if it is absent.
+    fn remove_in<Q>(tower: &Tower<K, V>, level: usize, key: &Q) -> Option<TowerPtr<K, V>>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let index = tower.child_for(key);
+        let child = &tower.children[index];
+
+        if child.has_key(key) {
+            // This is the top of the key's tower. It is not the first child,
+            // which shares this tower's key, so there is a left neighbour to
+            // take over what it owned on every level
+            let merged = Self::merge(&tower.children[index - 1], child, level - 1);
+
+            let mut children = tower.children[..index - 1].to_vec();
+            children.push(merged);
+            children.extend_from_slice(&tower.children[index + 1..]);
+            return Some(Tower::new(tower.entry.clone(), children));
+        }
+
+        if level == 1 {
+            return None;
+        }
+
+        let replacement = Self::remove_in(child, level - 1, key)?;
+        Some(tower.with_child(index, replacement))
+    }
+
+    /// Append what `right` owns below `level`, minus `right`'s own tower, to
+    /// its left neighbour `left`.
+    fn merge(left: &TowerPtr<K, V>, right: &Tower<K, V>, level: usize) -> TowerPtr<K, V> {
+        if level == 0 {
+            return Arc::clone(left);
+        }
+
+        let (last, init) = left.children.split_last().unwrap();
+        let mut children = init.to_vec();
+        children.push(Self::merge(last, &right.children[0], level - 1));
+        children.extend_from_slice(&right.children[1..]);
+
+        Tower::new(left.entry.clone(), children)
+    }
+
+    /// Iterate over the entries in key order.
+    pub fn iter(&self) -> PersistentIter<'_, K, V> {
+        PersistentIter {
+            stack: vec![self.root.children.iter()],
+            remaining: self.len,
+        }
+    }
+}
+
+impl<K, V> Clone for PersistentSkipList<K, V> {
+    /// Take a snapshot sharing all of this version's structure.
+    ///
+    /// Time complexity: O(1)
+    fn clone(&self) -> Self {
+        Self {
+            root: Arc::clone(&self.root),
+            level: self.level,
+            len: self.len,
+        }
+    }
+}
+
+impl<K: Key, V: Value> Default for PersistentSkipList<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Key + fmt::Debug, V: Value + fmt::Debug> fmt::Debug for PersistentSkipList<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+/// In-order iterator over a `PersistentSkipList`, a depth-first walk down to
+/// the level 0 towers.
+pub struct PersistentIter<'a, K, V> {
+    stack: Vec<slice::Iter<'a, TowerPtr<K, V>>>,
+    remaining: usize,
+}
+
+impl<'a, K, V> Iterator for PersistentIter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let Some(tower) = self.stack.last_mut()?.next() else {
+                self.stack.pop();
+                continue;
+            };
+
+            if !tower.children.is_empty() {
+                self.stack.push(tower.children.iter());
+            } else if let Some(entry) = &tower.entry {
+                self.remaining -= 1;
+                return Some((&entry.0, &entry.1));
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<K, V> ExactSizeIterator for PersistentIter<'_, K, V> {}
@@ -0,0 +1,135 @@
+use std::{cell::RefCell, fmt, mem::MaybeUninit, ptr::NonNull, rc::Rc};
+
+use crate::{ForwardPtr, Key, Node, NodePtr, SkipList, Value};
+
+/// A free list of node allocations that lists built with
+/// `SkipList::with_pool` draw from and return to.
+///
+/// Only the allocations are recycled: keys and values are still dropped as
+/// soon as their entry is removed. The pool is a cheap handle, so clones
+/// share the same free list and several lists can feed each other.
+///
+/// # Examples
+///
+/// ```
+/// use skiplist::{NodePool, SkipList};
+///
+/// let pool = NodePool::new();
+///
+/// let mut skip_list = SkipList::with_pool(&pool);
+/// for i in 0..100 {
+///     skip_list.insert(i, i);
+/// }
+/// drop(skip_list);
+/// assert_eq!(pool.len(), 100);
+///
+/// // A new list reuses those allocations
+/// let mut skip_list = SkipList::with_pool(&pool);
+/// skip_list.insert(1, 1);
+/// assert_eq!(pool.len(), 99);
+/// ```
+pub struct NodePool<K, V> {
+    nodes: Rc<RefCell<Vec<SpareNode<K, V>>>>,
+}
+
+/// A node allocation whose key and value are uninitialized.
+type SpareNode<K, V> = Box<Node<K, V>>;
+
+impl<K, V> NodePool<K, V> {
+    pub fn new() -> Self {
+        Self {
+            nodes: Rc::new(RefCell::new(Vec::new())),
+        }
+    }
+
+    /// Number of spare node allocations.
+    pub fn len(&self) -> usize {
+        self.nodes.borrow().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.borrow().is_empty()
+    }
+
+    /// Free every spare allocation.
+    pub fn clear(&self) {
+        self.nodes.borrow_mut().clear();
+    }
+
+    fn take(&self) -> Option<SpareNode<K, V>> {
+        self.nodes.borrow_mut().pop()
+    }
+
+    fn put(&self, node: SpareNode<K, V>) {
+        self.nodes.borrow_mut().push(node);
+    }
+}
+
+impl<K, V> Clone for NodePool<K, V> {
+    fn clone(&self) -> Self {
+        Self {
+            nodes: Rc::clone(&self.nodes),
+        }
+    }
+}
+
+impl<K, V> Default for NodePool<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V> fmt::Debug for NodePool<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NodePool")
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+impl<K: Key, V: Value> SkipList<K, V> {
+    /// Create an empty list whose nodes are taken from `pool` when possible,
+    /// and handed back to it when they are removed or the list is dropped.
+    pub fn with_pool(pool: &NodePool<K, V>) -> Self {
+        let mut skip_list = Self::new();
+        skip_list.pool = Some(pool.clone());
+        skip_list
+    }
+
+    /// Allocate a node holding `key` and `value` with `level + 1` unlinked
+    /// forward pointers, reusing a pooled allocation if there is one.
+    pub(crate) fn alloc_node(&self, key: K, value: V, level: usize) -> NodePtr<K, V> {
+        let node = match self.pool.as_ref().and_then(NodePool::take) {
+            Some(mut node) => {
+                node.key = MaybeUninit::new(key);
+                node.value = MaybeUninit::new(value);
+                node.forward.resize(level + 1, ForwardPtr::default());
+                node.level = level;
+                node
+            }
+            None => Box::new(Node {
+                key: MaybeUninit::new(key),
+                value: MaybeUninit::new(value),
+                forward: vec![ForwardPtr::default(); level + 1],
+                level,
+            }),
+        };
+
+        NonNull::from(Box::leak(node))
+    }
+
+    /// Take the key and value out of an unlinked node and release its
+    /// allocation, to the pool if there is one.
+    pub(crate) fn free_node(&self, node: NodePtr<K, V>) -> (K, V) {
+        let mut node = unsafe { Box::from_raw(node.as_ptr()) };
+        let key = std::mem::replace(&mut node.key, MaybeUninit::uninit());
+        let value = std::mem::replace(&mut node.value, MaybeUninit::uninit());
+
+        if let Some(pool) = &self.pool {
+            node.forward.clear();
+            pool.put(node);
+        }
+
+        unsafe { (key.assume_init(), value.assume_init()) }
+    }
+}
@@ -0,0 +1,247 @@
+use crate::{Key, NodePtr, SkipList, Value};
+
+/// Single left-to-right pass over level 0 that decides, node by node, whether
+/// to keep or remove it, relinking every level around the removed ones.
+///
+/// For each level it remembers the last kept node and the original position
+/// of the node that one currently points at, so whenever the splicer is
+/// dropped (after a full pass, an early stop, or a panic in a user callback)
+/// the remaining spans can be fixed in O(levels) and the list is left valid.
+pub(crate) struct Splicer<'a, K: Key, V: Value> {
+    skip_list: &'a mut SkipList<K, V>,
+    /// Last kept node on each level
+    last: Vec<NodePtr<K, V>>,
+    /// New position of `last[i]` (head is position 0)
+    last_pos: Vec<usize>,
+    /// Original position of the node `last[i]` points at on level `i`
+    target_pos: Vec<usize>,
+    /// Next node to visit
+    cur: NodePtr<K, V>,
+    /// Original 0-based index of `cur`
+    index: usize,
+    /// Nodes removed so far
+    removed: usize,
+}
+
+impl<'a, K: Key, V: Value> Splicer<'a, K, V> {
+    pub(crate) fn new(skip_list: &'a mut SkipList<K, V>) -> Self {
+        let head = unsafe { skip_list.head.as_ref() };
+        let levels = skip_list.level + 1;
+
+        Self {
+            last: vec![skip_list.head; levels],
+            last_pos: vec![0; levels],
+            target_pos: head.forward.iter().map(|f| f.span).collect(),
+            cur: head.forward[0].ptr,
+            index: 0,
+            removed: 0,
+            skip_list,
+        }
+    }
+
+    /// The next node to visit, or `None` once the pass reached the tail.
+    pub(crate) fn current(&mut self) -> Option<NodePtr<K, V>> {
+        if self.skip_list.is_tail(self.cur) {
+            None
+        } else {
+            Some(self.cur)
+        }
+    }
+
+    /// Original 0-based index of the current node.
+    pub(crate) fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Keep the current node and move on to the next one.
+    pub(crate) fn keep(&mut self) {
+        let mut node = self.cur;
+        let node_ref = unsafe { node.as_mut() };
+        let orig_pos = self.index + 1;
+        let new_pos = orig_pos - self.removed;
+
+        for i in 0..=node_ref.level {
+            unsafe { self.last[i].as_mut() }.forward[i].span = new_pos - self.last_pos[i];
+            self.last[i] = node;
+            self.last_pos[i] = new_pos;
+            self.target_pos[i] = orig_pos + node_ref.forward[i].span;
+        }
+
+        self.cur = node_ref.forward[0].ptr;
+        self.index += 1;
+    }
+
+    /// Unlink the current node from every level, move on to the next one and
+    /// hand back the removed entry.
+    pub(crate) fn remove(&mut self) -> (K, V) {
+        let node = self.cur;
+        let node_ref = unsafe { node.as_ref() };
+        let orig_pos = self.index + 1;
+
+        for i in 0..=node_ref.level {
+            unsafe { self.last[i].as_mut() }.forward[i].ptr = node_ref.forward[i].ptr;
+            self.target_pos[i] = orig_pos + node_ref.forward[i].span;
+        }
+
+        self.cur = node_ref.forward[0].ptr;
+        self.index += 1;
+        self.removed += 1;
+        self.skip_list.len -= 1;
+
+        self.skip_list.free_node(node)
+    }
+}
+
+impl<K: Key, V: Value> Drop for Splicer<'_, K, V> {
+    fn drop(&mut self) {
+        // Every node not visited yet is kept, so its new position is simply
+        // its original one minus the number of removed nodes.
+        for i in 0..=self.skip_list.level {
+            unsafe { self.last[i].as_mut() }.forward[i].span =
+                self.target_pos[i] - self.removed - self.last_pos[i];
+        }
+
+        self.skip_list.trim_levels();
+        self.skip_list.after_removal();
+    }
+}
+
+impl<K: Key, V: Value> SkipList<K, V> {
+    /// Keep only the entries for which `f` returns `true`.
+    ///
+    /// Time complexity: O(n)
+    pub fn retain<F: FnMut(&K, &mut V) -> bool>(&mut self, mut f: F) {
+        self.retain_indexed(|_, key, value| f(key, value));
+    }
+
+    /// Keep only the entries for which `f` returns `true`, and return how
+    /// many were removed.
+    ///
+    /// Time complexity: O(n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::SkipList;
+    ///
+    /// let mut skip_list = SkipList::from_sorted((0..6).map(|i| (i, i)));
+    ///
+    /// assert_eq!(skip_list.retain_count(|&k, _| k < 4), 2);
+    /// assert_eq!(skip_list.retain_count(|&k, _| k < 4), 0);
+    /// ```
+    pub fn retain_count<F: FnMut(&K, &mut V) -> bool>(&mut self, f: F) -> usize {
+        let before = self.len;
+        self.retain(f);
+        before - self.len
+    }
+
+    /// Keep only the entries for which `f` returns `true`, and return the
+    /// removed ones in sorted order.
+    ///
+    /// Time complexity: O(n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::SkipList;
+    ///
+    /// let mut skip_list = SkipList::from_sorted((0..6).map(|i| (i, i)));
+    /// let removed = skip_list.retain_returning(|&k, _| k < 4);
+    ///
+    /// assert_eq!(removed, vec![(4, 4), (5, 5)]);
+    /// assert_eq!(skip_list.len(), 4);
+    /// ```
+    pub fn retain_returning<F: FnMut(&K, &mut V) -> bool>(&mut self, mut f: F) -> Vec<(K, V)> {
+        let mut removed = Vec::new();
+        self.splice_with(|_, key, value| f(key, value), |entry| removed.push(entry));
+        removed
+    }
+
+    /// Keep only the entries for which `f` returns `true`, also passing each
+    /// entry's index to `f`.
+    ///
+    /// The index is the entry's position *before* this call, so it counts up
+    /// by one for every visited entry regardless of how many were already
+    /// removed. All entries are visited in a single pass over level 0.
+    ///
+    /// Time complexity: O(n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::SkipList;
+    ///
+    /// let mut skip_list = SkipList::from_sorted((10..15).map(|i| (i, i)));
+    /// skip_list.retain_indexed(|index, _, _| index % 2 == 0);
+    ///
+    /// let keys: Vec<_> = skip_list.iter().map(|(&k, _)| k).collect();
+    /// assert_eq!(keys, vec![10, 12, 14]);
+    /// ```
+    pub fn retain_indexed<F: FnMut(usize, &K, &mut V) -> bool>(&mut self, f: F) {
+        self.splice_with(f, drop);
+    }
+
+    /// Remove every entry whose value equals that of the entry right before
+    /// it, keeping the first of each run of equal values, like `Vec::dedup`
+    /// over the values in key order.
+    ///
+    /// Time complexity: O(n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::SkipList;
+    ///
+    /// let mut skip_list = SkipList::from_sorted([(1, 'a'), (2, 'a'), (3, 'b'), (4, 'a')]);
+    /// skip_list.dedup_by_value();
+    ///
+    /// let keys: Vec<_> = skip_list.iter().map(|(&k, _)| k).collect();
+    /// assert_eq!(keys, vec![1, 3, 4]);
+    /// ```
+    pub fn dedup_by_value(&mut self)
+    where
+        V: PartialEq,
+    {
+        let mut splicer = Splicer::new(self);
+        let mut previous: Option<NodePtr<K, V>> = None;
+
+        while let Some(node) = splicer.current() {
+            // The previous node was kept, so it is still allocated
+            let duplicate = previous.is_some_and(|previous| unsafe {
+                previous.as_ref().value.assume_init_ref() == node.as_ref().value.assume_init_ref()
+            });
+
+            if duplicate {
+                splicer.remove();
+            } else {
+                previous = Some(node);
+                splicer.keep();
+            }
+        }
+    }
+
+    /// One `Splicer` pass asking `f` about every entry, with its original
+    /// index, and handing each removed entry to `on_remove`.
+    fn splice_with(
+        &mut self,
+        mut f: impl FnMut(usize, &K, &mut V) -> bool,
+        mut on_remove: impl FnMut((K, V)),
+    ) {
+        let mut splicer = Splicer::new(self);
+
+        while let Some(mut node) = splicer.current() {
+            let node_ref = unsafe { node.as_mut() };
+            let keep = f(
+                splicer.index(),
+                unsafe { node_ref.key.assume_init_ref() },
+                unsafe { node_ref.value.assume_init_mut() },
+            );
+
+            if keep {
+                splicer.keep();
+            } else {
+                on_remove(splicer.remove());
+            }
+        }
+    }
+}
@@ -0,0 +1,82 @@
+use std::mem::size_of;
+
+use crate::{ForwardPtr, Key, Node, SkipList, Value};
+
+/// Shape and size metrics of a list, as returned by `SkipList::stats`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SkipListStats {
+    /// Number of entries.
+    pub len: usize,
+    /// Highest level in use; 0 when only the bottom level has nodes.
+    pub level: usize,
+    /// Number of levels in use, `level + 1`.
+    pub height: usize,
+    /// Mean top level of the entries' towers, 0.0 for an empty list.
+    pub avg_level: f64,
+    /// Longest jump, in entries, between neighbours on the highest level,
+    /// counting the links out of the head and into the tail.
+    pub max_span_at_top: usize,
+    /// Estimated heap and inline size of the list's own structure: the list
+    /// header, every node including the sentinels, and their forward pointer
+    /// buffers. Heap memory owned by keys and values is not included, nor are
+    /// spare allocations held by a `NodePool`.
+    pub memory_bytes: usize,
+}
+
+impl<K: Key, V: Value> SkipList<K, V> {
+    /// Collect health metrics about the list's shape in one call.
+    ///
+    /// Time complexity: O(n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::SkipList;
+    ///
+    /// let skip_list = SkipList::from_sorted((0..100).map(|i| (i, i)));
+    /// let stats = skip_list.stats();
+    ///
+    /// assert_eq!(stats.len, 100);
+    /// assert_eq!(stats.height, stats.level + 1);
+    /// assert!(stats.max_span_at_top <= 101);
+    /// ```
+    pub fn stats(&self) -> SkipListStats {
+        let node_size = |node: &Node<K, V>| {
+            size_of::<Node<K, V>>() + node.forward.capacity() * size_of::<ForwardPtr<K, V>>()
+        };
+
+        // Every node is on level 0, so one walk there sees all towers
+        let mut level_sum = 0;
+        let mut memory_bytes = size_of::<Self>()
+            + node_size(unsafe { self.head.as_ref() })
+            + node_size(unsafe { self.tail.as_ref() });
+        let mut cur = unsafe { self.head.as_ref() }.forward[0].ptr;
+        while !self.is_tail(cur) {
+            let node = unsafe { cur.as_ref() };
+            level_sum += node.level;
+            memory_bytes += node_size(node);
+            cur = node.forward[0].ptr;
+        }
+
+        let mut max_span_at_top = 0;
+        let mut cur = self.head;
+        while !self.is_tail(cur) {
+            let forward_ptr = unsafe { cur.as_ref() }.forward[self.level];
+            max_span_at_top = max_span_at_top.max(forward_ptr.span);
+            cur = forward_ptr.ptr;
+        }
+
+        SkipListStats {
+            len: self.len,
+            level: self.level,
+            height: self.level + 1,
+            avg_level: if self.len == 0 {
+                0.0
+            } else {
+                level_sum as f64 / self.len as f64
+            },
+            max_span_at_top,
+            memory_bytes,
+        }
+    }
+}
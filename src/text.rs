@@ -0,0 +1,107 @@
+use std::{
+    fmt::Display,
+    io::{self, BufRead, Write},
+    str::FromStr,
+};
+
+use crate::{Key, SkipList, Value};
+
+fn invalid_data(line: usize, message: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("line {}: {}", line, message),
+    )
+}
+
+impl<K: Key, V: Value> SkipList<K, V> {
+    /// Stream the entries to `w` in sorted order, one `key\tvalue` line each,
+    /// without building the serialized form in memory.
+    ///
+    /// The `Display` form of a key must not contain a tab or a newline, nor
+    /// that of a value a newline, for `read_from` to read the output back.
+    /// Every line is a separate write, so wrap unbuffered writers such as
+    /// files in a `BufWriter`.
+    ///
+    /// Time complexity: O(n)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use skiplist::SkipList;
+    ///
+    /// let skip_list = SkipList::from_sorted([(1, "one"), (2, "two")]);
+    /// let mut out = Vec::new();
+    /// skip_list.write_to(&mut out).unwrap();
+    ///
+    /// assert_eq!(out, b"1\tone\n2\ttwo\n");
+    /// ```
+    pub fn write_to<W: Write>(&self, w: &mut W) -> io::Result<()>
+    where
+        K: Display,
+        V: Display,
+    {
+        for (key, value) in self.iter() {
+            writeln!(w, "{}\t{}", key, value)?;
+        }
+
+        Ok(())
+    }
+
+    /// Read a list written by `write_to`: one `key\tvalue` line per entry,
+    /// split at the first tab, in strictly ascending key order.
+    ///
+    /// Lines are parsed and linked in as they are read, through
+    /// `from_sorted`. A line without a tab, a key or value that does not
+    /// parse, or a key not greater than the one before it is reported as
+    /// `InvalidData`, naming the 1-based line.
+    ///
+    /// Time complexity: O(n) expected
+    pub fn read_from<R: BufRead>(reader: R) -> io::Result<Self>
+    where
+        K: FromStr,
+        V: FromStr,
+    {
+        let parse = |line: &str, number: usize| -> io::Result<(K, V)> {
+            let (key, value) = line
+                .split_once('\t')
+                .ok_or_else(|| invalid_data(number, "expected a tab between key and value"))?;
+            let key = key
+                .parse()
+                .map_err(|_| invalid_data(number, "invalid key"))?;
+            let value = value
+                .parse()
+                .map_err(|_| invalid_data(number, "invalid value"))?;
+            Ok((key, value))
+        };
+
+        let mut lines = reader.lines().zip(1..);
+        let mut error = None;
+        let list = Self::from_sorted(std::iter::from_fn(|| {
+            let (line, number) = lines.next()?;
+            match line.and_then(|line| parse(&line, number)) {
+                Ok(entry) => Some(entry),
+                Err(e) => {
+                    error = Some(e);
+                    None
+                }
+            }
+        }));
+        if let Some(e) = error {
+            return Err(e);
+        }
+
+        // The keys were moved into the list, so check their order there
+        if let Some(index) = list
+            .iter()
+            .zip(list.iter().skip(1))
+            .position(|((a, _), (b, _))| a >= b)
+        {
+            return Err(invalid_data(
+                index + 2,
+                "key is not greater than the one before it",
+            ));
+        }
+
+        Ok(list)
+    }
+}
@@ -0,0 +1,218 @@
+use rand::{SeedableRng, rngs::StdRng};
+use skiplist::{BuildError, SkipList, SkipListBuilder};
+
+/// Number of levels shown in the tower diagram.
+fn tower_height<K: Ord + std::fmt::Debug, V: std::fmt::Debug>(skip_list: &SkipList<K, V>) -> usize {
+    skip_list.format_tower()
+        .lines()
+        .filter(|line| line.starts_with('L'))
+        .count()
+}
+
+#[test]
+fn test_builder_defaults() {
+    let mut skip_list = SkipListBuilder::new().build().unwrap();
+
+    for i in 0..100 {
+        skip_list.insert(i, i * 10);
+    }
+
+    assert_eq!(skip_list.len(), 100);
+    for i in 0..100 {
+        assert_eq!(skip_list.index(i as usize), Some((&i, &(i * 10))));
+    }
+}
+
+#[test]
+fn test_builder_seeded_rng_is_reproducible() {
+    let build = |seed| {
+        let mut skip_list = SkipListBuilder::new()
+            .rng(StdRng::seed_from_u64(seed))
+            .build()
+            .unwrap();
+        for i in [5, 3, 9, 1, 7, 2, 8, 4, 6, 0] {
+            skip_list.insert(i, i);
+        }
+        skip_list
+    };
+
+    // Same seed, same tower and spans
+    assert_eq!(build(42).format_tower(), build(42).format_tower());
+    assert_eq!(build(7).format_tower(), build(7).format_tower());
+}
+
+#[test]
+fn test_builder_max_level() {
+    let mut skip_list = SkipListBuilder::new().max_level(0).build().unwrap();
+
+    for i in 0..200 {
+        skip_list.insert(i, i);
+    }
+
+    // Every node stays on level 0
+    assert_eq!(tower_height(&skip_list), 1);
+    assert_eq!(skip_list.index(150), Some((&150, &150)));
+
+    let mut skip_list = SkipListBuilder::new().max_level(2).build().unwrap();
+    for i in 0..200 {
+        skip_list.insert(i, i);
+    }
+    assert!(tower_height(&skip_list) <= 3);
+}
+
+#[test]
+fn test_builder_probability() {
+    let height_with = |probability| {
+        let mut skip_list = SkipListBuilder::new()
+            .probability(probability)
+            .rng(StdRng::seed_from_u64(1))
+            .build()
+            .unwrap();
+        for i in 0..1000 {
+            skip_list.insert(i, i);
+        }
+        tower_height(&skip_list)
+    };
+
+    assert!(height_with(0.05) < height_with(0.9));
+}
+
+#[test]
+fn test_builder_expected_len() {
+    let mut skip_list = SkipListBuilder::new().expected_len(10_000).build().unwrap();
+
+    assert!(skip_list.is_empty());
+    for i in 0..100 {
+        skip_list.insert(i, i);
+    }
+    assert_eq!(skip_list.len(), 100);
+}
+
+#[test]
+fn test_builder_rejects_invalid_probability() {
+    for probability in [0.0, 1.0, -0.5, 1.5, f64::NAN] {
+        let result = SkipListBuilder::<i32, i32>::new()
+            .probability(probability)
+            .build();
+        assert!(matches!(result, Err(BuildError::InvalidProbability(_))));
+    }
+}
+
+#[test]
+fn test_builder_dynamic_max_level_small_list() {
+    for seed in 0..20 {
+        let mut skip_list = SkipListBuilder::new()
+            .dynamic_max_level(true)
+            .rng(StdRng::seed_from_u64(seed))
+            .build()
+            .unwrap();
+        for i in 0..10 {
+            skip_list.insert(i, i);
+            // Never taller than ceil(log2(len)) + 1 levels
+            let cap = ((skip_list.len() as f64).log2().ceil() as usize) + 1;
+            assert!(tower_height(&skip_list) <= cap);
+        }
+        assert!(tower_height(&skip_list) <= 5);
+    }
+}
+
+// Towers this tall make every `paranoid` check slow
+#[test]
+#[cfg(not(feature = "paranoid"))]
+fn test_builder_dynamic_max_level_grows_past_32() {
+    // With p = 0.5 the dynamic cap only passes 32 beyond 2^32 entries, so use
+    // a high probability: 2300 entries give a cap of ceil(log_{1/0.9} 2301) = 74
+    let build = |dynamic| {
+        let mut skip_list = SkipListBuilder::new()
+            .probability(0.9)
+            .dynamic_max_level(dynamic)
+            .rng(StdRng::seed_from_u64(3))
+            .build()
+            .unwrap();
+        for i in 0..2300 {
+            skip_list.insert(i, i);
+        }
+        skip_list
+    };
+
+    let dynamic = build(true);
+    assert!(tower_height(&dynamic) > 33);
+    assert_eq!(dynamic.index(1_000), Some((&1_000, &1_000)));
+
+    // The static cap keeps the same list at 32 levels above the base
+    assert_eq!(tower_height(&build(false)), 33);
+}
+
+// 20k single inserts, each followed by a full check under `paranoid`
+#[cfg(not(feature = "paranoid"))]
+#[test]
+fn test_insert_tracked_level_distribution() {
+    let mut skip_list = SkipListBuilder::new()
+        .probability(0.5)
+        .rng(StdRng::seed_from_u64(7))
+        .build()
+        .unwrap();
+
+    let n = 20_000;
+    let mut levels = Vec::with_capacity(n);
+    for i in 0..n {
+        let (old, level) = skip_list.insert_tracked(i, i);
+        assert_eq!(old, None);
+        levels.push(level);
+    }
+
+    // A node reaches level k or more with probability 0.5^k
+    for k in 1..=5 {
+        let observed = levels.iter().filter(|&&level| level >= k).count() as f64 / n as f64;
+        let expected = 0.5f64.powi(k as i32);
+        assert!(
+            (observed - expected).abs() < 0.2 * expected,
+            "level >= {}: observed {:.4}, expected {:.4}",
+            k,
+            observed,
+            expected
+        );
+    }
+
+    // Replacing reports the existing node's level
+    for (i, &level) in levels.iter().enumerate().step_by(97) {
+        assert_eq!(skip_list.insert_tracked(i, 0), (Some(i), level));
+    }
+    assert_eq!(skip_list.len(), n);
+}
+
+#[test]
+fn test_from_iter_with() {
+    let keys: Vec<u32> = (0..2000u32).map(|i| i.wrapping_mul(2654435761)).collect();
+    let build = |probability| {
+        let config = SkipListBuilder::new()
+            .probability(probability)
+            .rng(StdRng::seed_from_u64(5));
+        SkipList::from_iter_with(keys.iter().map(|&k| (k, ())), config).unwrap()
+    };
+
+    // 2000 entries give an expected height of log_4 2001 ~ 5 at 0.25 and
+    // log_2 2001 ~ 11 at 0.5
+    let sparse = build(0.25);
+    let dense = build(0.5);
+    assert!(sparse.iter().eq(dense.iter()));
+    assert!(
+        sparse.stats().level + 3 < dense.stats().level,
+        "{} vs {}",
+        sparse.stats().level,
+        dense.stats().level
+    );
+
+    // Same as the default collect, and later duplicates win
+    let collected: SkipList<_, _> = [(2, 'a'), (1, 'b'), (2, 'c')].into_iter().collect();
+    let configured = SkipList::from_iter_with([(2, 'a'), (1, 'b'), (2, 'c')], SkipListBuilder::new()).unwrap();
+    assert!(collected.iter().eq(configured.iter()));
+    assert_eq!(collected.get(&2), Some(&'c'));
+
+    // An invalid configuration is reported, not collected into
+    let config = SkipListBuilder::new().probability(1.5);
+    assert_eq!(
+        SkipList::from_iter_with([(1, 1)], config).unwrap_err(),
+        BuildError::InvalidProbability(1.5)
+    );
+}
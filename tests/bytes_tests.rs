@@ -0,0 +1,90 @@
+use skiplist::{DecodeError, FromBytes, SkipList, ToBytes};
+
+#[test]
+fn test_bytes_round_trip_u32() {
+    let mut skip_list = SkipList::new();
+    for i in (0..500u32).rev() {
+        skip_list.insert(i * 3, i.wrapping_mul(2654435761));
+    }
+
+    let bytes = skip_list.to_bytes();
+    let decoded = SkipList::<u32, u32>::from_bytes(&bytes).unwrap();
+
+    assert_eq!(decoded.len(), skip_list.len());
+    assert!(decoded.iter().eq(skip_list.iter()));
+    assert_eq!(decoded.index(100), skip_list.index(100));
+
+    let empty = SkipList::<u32, u32>::new();
+    let decoded = SkipList::<u32, u32>::from_bytes(&empty.to_bytes()).unwrap();
+    assert!(decoded.is_empty());
+}
+
+/// Variable-length value with its own encoding
+#[derive(Debug, PartialEq)]
+struct Label(String);
+
+impl ToBytes for Label {
+    fn to_bytes(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(self.0.as_bytes());
+    }
+}
+
+impl FromBytes for Label {
+    fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        String::from_utf8(bytes.to_vec()).ok().map(Label)
+    }
+}
+
+#[test]
+fn test_bytes_round_trip_custom_value() {
+    let mut skip_list = SkipList::new();
+    for i in 0..50u32 {
+        skip_list.insert(i, Label("x".repeat(i as usize)));
+    }
+
+    let decoded = SkipList::<u32, Label>::from_bytes(&skip_list.to_bytes()).unwrap();
+    assert!(decoded.iter().eq(skip_list.iter()));
+}
+
+#[test]
+fn test_bytes_rejects_invalid_input() {
+    let skip_list = SkipList::from_sorted((0..10u32).map(|i| (i, i)));
+    let bytes = skip_list.to_bytes();
+
+    for len in [0, 7, 8, 20, bytes.len() - 1] {
+        assert_eq!(
+            SkipList::<u32, u32>::from_bytes(&bytes[..len]).err(),
+            Some(DecodeError::Truncated)
+        );
+    }
+
+    let mut trailing = bytes.clone();
+    trailing.push(0);
+    assert_eq!(
+        SkipList::<u32, u32>::from_bytes(&trailing).err(),
+        Some(DecodeError::TrailingBytes)
+    );
+
+    // A u32 key decoded as u64 has the wrong width
+    assert_eq!(
+        SkipList::<u64, u32>::from_bytes(&bytes).err(),
+        Some(DecodeError::InvalidItem(0))
+    );
+
+    // Swap the keys of the first two entries (each entry is 2 * (8 + 4) bytes)
+    let mut unsorted = bytes.clone();
+    let first_key = 8 + 8;
+    let second_key = first_key + 24;
+    unsorted[first_key] = 1;
+    unsorted[second_key] = 0;
+    assert_eq!(
+        SkipList::<u32, u32>::from_bytes(&unsorted).err(),
+        Some(DecodeError::Unsorted(1))
+    );
+
+    // Huge entry count with no data behind it
+    assert_eq!(
+        SkipList::<u32, u32>::from_bytes(&u64::MAX.to_le_bytes()).err(),
+        Some(DecodeError::Truncated)
+    );
+}
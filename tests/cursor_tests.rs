@@ -0,0 +1,194 @@
+use skiplist::SkipList;
+
+#[test]
+fn test_cursor_seek_and_read() {
+    let mut skip_list = SkipList::new();
+    for i in 0..20 {
+        skip_list.insert(i * 10, i);
+    }
+
+    let mut cursor = skip_list.cursor_at(&55);
+    assert_eq!(cursor.current(), Some((&60, &6)));
+    assert_eq!(cursor.peek_next(), Some((&70, &7)));
+
+    cursor.move_next();
+    cursor.move_next();
+    assert_eq!(cursor.current(), Some((&80, &8)));
+
+    // Re-seek to an earlier key
+    cursor.seek(&10);
+    assert_eq!(cursor.current(), Some((&10, &1)));
+
+    // Exact matches land on the key itself
+    cursor.seek(&150);
+    assert_eq!(cursor.current(), Some((&150, &15)));
+}
+
+#[test]
+fn test_cursor_past_the_end() {
+    let mut skip_list = SkipList::new();
+    for i in 0..5 {
+        skip_list.insert(i, i);
+    }
+
+    let mut cursor = skip_list.cursor_at(&4);
+    assert_eq!(cursor.current(), Some((&4, &4)));
+    assert_eq!(cursor.peek_next(), None);
+
+    cursor.move_next();
+    assert_eq!(cursor.current(), None);
+    assert_eq!(cursor.peek_next(), None);
+
+    // Moving past the end is a no-op
+    cursor.move_next();
+    assert_eq!(cursor.current(), None);
+
+    let cursor = skip_list.cursor_at(&100);
+    assert_eq!(cursor.current(), None);
+
+    let cursor = skip_list.cursor_at(&-100);
+    assert_eq!(cursor.current(), Some((&0, &0)));
+
+    let empty: SkipList<i32, i32> = SkipList::new();
+    assert_eq!(empty.cursor_at(&0).current(), None);
+}
+
+#[test]
+fn test_cursor_walk_matches_iter() {
+    let mut skip_list = SkipList::new();
+    for i in [42, 17, 8, 23, 4, 15, 31] {
+        skip_list.insert(i, i * 2);
+    }
+
+    let mut cursor = skip_list.cursor_at(&0);
+    let mut walked = vec![];
+    while let Some(entry) = cursor.current() {
+        walked.push(entry);
+        cursor.move_next();
+    }
+
+    assert_eq!(walked, skip_list.iter().collect::<Vec<_>>());
+}
+
+#[test]
+fn test_lower_and_upper_bound() {
+    let skip_list = SkipList::from_sorted([(3, 'a'), (5, 'b'), (7, 'c')]);
+    assert_eq!(skip_list.lower_bound(&5).current(), Some((&5, &'b')));
+    assert_eq!(skip_list.upper_bound(&5).current(), Some((&7, &'c')));
+    assert_eq!(skip_list.lower_bound(&0).rank(), 0);
+    assert_eq!(skip_list.upper_bound(&7).current(), None);
+    assert_eq!(skip_list.upper_bound(&7).rank(), 3);
+
+    let skip_list = SkipList::from_sorted([(3, 'a'), (7, 'c')]);
+    assert_eq!(skip_list.lower_bound(&5).current(), Some((&7, &'c')));
+    assert_eq!(skip_list.upper_bound(&5).current(), Some((&7, &'c')));
+}
+
+#[test]
+fn test_cursor_move_prev_and_rank() {
+    let skip_list = SkipList::from_sorted((0..50).map(|i| (i * 2, i)));
+
+    let mut cursor = skip_list.lower_bound(&41);
+    assert_eq!(cursor.current(), Some((&42, &21)));
+    assert_eq!(cursor.rank(), 21);
+
+    cursor.move_prev();
+    assert_eq!(cursor.current(), Some((&40, &20)));
+    assert_eq!(cursor.rank(), 20);
+    cursor.move_next();
+    cursor.move_next();
+    assert_eq!(cursor.rank(), 22);
+    assert_eq!(cursor.current(), Some((&44, &22)));
+
+    // Walk all the way back to the front, where move_prev stops
+    for rank in (0..22).rev() {
+        cursor.move_prev();
+        assert_eq!(cursor.rank(), rank);
+        assert_eq!(cursor.current(), Some((&(rank * 2), &rank)));
+    }
+    cursor.move_prev();
+    assert_eq!(cursor.rank(), 0);
+    assert_eq!(cursor.current(), Some((&0, &0)));
+
+    // From past the end, move_prev lands on the last entry
+    let mut cursor = skip_list.upper_bound(&1000);
+    assert_eq!(cursor.rank(), 50);
+    cursor.move_prev();
+    assert_eq!(cursor.current(), Some((&98, &49)));
+
+    // seek keeps the rank in step
+    cursor.seek(&10);
+    assert_eq!(cursor.rank(), 5);
+
+    let empty = SkipList::<i32, i32>::new();
+    let mut cursor = empty.lower_bound(&0);
+    cursor.move_prev();
+    assert_eq!((cursor.current(), cursor.rank()), (None, 0));
+}
+
+#[test]
+fn test_insert_cursor_matches_insert() {
+    use rand::{Rng, SeedableRng, rngs::StdRng};
+    use std::collections::BTreeMap;
+
+    let mut rng = StdRng::seed_from_u64(7);
+    let mut skip_list = SkipList::new();
+    let mut expected = BTreeMap::new();
+
+    // Mostly increasing keys, with repeats and the odd jump backwards
+    let mut cursor = skip_list.insert_cursor();
+    let mut key = 0;
+    for i in 0..2000 {
+        key = match rng.random_range(0..10) {
+            0 => rng.random_range(0..=key),
+            1 => key,
+            _ => key + rng.random_range(1..5),
+        };
+        assert_eq!(cursor.insert(key, i), expected.insert(key, i));
+
+        let probe = rng.random_range(0..=key + 2);
+        assert_eq!(cursor.get(&probe), expected.get(&probe));
+    }
+    *cursor.get_or_insert_with(3, || 0) += 100;
+    *expected.entry(3).or_insert(0) += 100;
+    *cursor.get_or_insert_with(key + 1, || 0) += 100;
+    *expected.entry(key + 1).or_insert(0) += 100;
+    drop(cursor);
+
+    skip_list.assert_invariants();
+    assert!(skip_list.iter().eq(expected.iter()));
+}
+
+#[test]
+fn test_insert_cursor_on_existing_list() {
+    let mut skip_list = SkipList::from_sorted((0..50).map(|i| (i * 2, i)));
+
+    let mut cursor = skip_list.insert_cursor();
+    for i in 0..100 {
+        cursor.insert(i, 0);
+    }
+    drop(cursor);
+
+    skip_list.assert_invariants();
+    assert_eq!(skip_list.len(), 100);
+    assert!(skip_list.iter().all(|(_, &v)| v == 0));
+    assert_eq!(skip_list.index(37), Some((&37, &0)));
+}
+
+#[test]
+fn test_insert_cursor_far_jumps() {
+    let mut skip_list = SkipList::from_sorted((0..1000).map(|i| (i * 2, i)));
+
+    // Short hops climb only a few levels, jumps across the whole list climb
+    // past the top and restart from the head
+    let mut cursor = skip_list.insert_cursor();
+    for key in [1, 3, 1001, 1003, 1997, 1999, 5, 1501] {
+        assert_eq!(cursor.get(&(key - 1)), Some(&((key - 1) / 2)));
+        assert_eq!(cursor.insert(key, 0), None);
+    }
+    drop(cursor);
+
+    skip_list.assert_invariants();
+    assert_eq!(skip_list.len(), 1008);
+    assert_eq!(skip_list.index(2), Some((&2, &1)));
+}
@@ -0,0 +1,81 @@
+use skiplist::{NodePool, SkipList};
+use std::cell::Cell;
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::rc::Rc;
+
+/// Counts its drops in a shared counter and panics on the drop that brings
+/// the counter to `panic_on`.
+struct PanicOnDrop {
+    drops: Rc<Cell<usize>>,
+    panic_on: usize,
+}
+
+impl Drop for PanicOnDrop {
+    fn drop(&mut self) {
+        self.drops.set(self.drops.get() + 1);
+        if self.drops.get() == self.panic_on {
+            panic!("drop number {}", self.panic_on);
+        }
+    }
+}
+
+fn build(pool: &NodePool<i32, PanicOnDrop>, drops: &Rc<Cell<usize>>, n: i32) -> SkipList<i32, PanicOnDrop> {
+    let mut skip_list = SkipList::with_pool(pool);
+    for i in 0..n {
+        skip_list.insert(
+            i,
+            PanicOnDrop {
+                drops: drops.clone(),
+                panic_on: 3,
+            },
+        );
+    }
+    skip_list
+}
+
+#[test]
+fn test_drop_list_with_panicking_value() {
+    let drops = Rc::new(Cell::new(0));
+    let pool = NodePool::new();
+    let skip_list = build(&pool, &drops, 10);
+
+    let result = catch_unwind(AssertUnwindSafe(|| drop(skip_list)));
+    assert!(result.is_err());
+
+    // Every node went back to the pool and every value was still dropped
+    assert_eq!(pool.len(), 10);
+    assert_eq!(drops.get(), 10);
+}
+
+#[test]
+fn test_drop_into_iter_with_panicking_value() {
+    let drops = Rc::new(Cell::new(0));
+    let pool = NodePool::new();
+    let skip_list = build(&pool, &drops, 10);
+
+    let mut iter = skip_list.into_iter();
+    drop(iter.next());
+    assert_eq!(iter.len(), 9);
+
+    let result = catch_unwind(AssertUnwindSafe(|| drop(iter)));
+    assert!(result.is_err());
+    assert_eq!(pool.len(), 10);
+    assert_eq!(drops.get(), 10);
+}
+
+#[test]
+fn test_drop_into_iter_item_with_panicking_value() {
+    let drops = Rc::new(Cell::new(0));
+    let pool = NodePool::new();
+    let skip_list = build(&pool, &drops, 10);
+
+    // The panic happens while the loop body drops a yielded item
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        for entry in skip_list {
+            drop(entry);
+        }
+    }));
+    assert!(result.is_err());
+    assert_eq!(pool.len(), 10);
+    assert_eq!(drops.get(), 10);
+}
@@ -0,0 +1,249 @@
+use skiplist::{Entry, SkipList};
+
+#[test]
+fn test_entry_or_insert() {
+    let mut skip_list = SkipList::new();
+
+    *skip_list.entry("a").or_insert(0) += 1;
+    *skip_list.entry("b").or_insert(0) += 1;
+    *skip_list.entry("a").or_insert(0) += 1;
+
+    assert_eq!(skip_list.len(), 2);
+    assert_eq!(skip_list.get(&"a"), Some(&2));
+    assert_eq!(skip_list.get(&"b"), Some(&1));
+
+    *skip_list.entry("c").or_default() += 5;
+    assert_eq!(skip_list.get(&"c"), Some(&5));
+
+    let value = skip_list.entry("d").or_insert_with(|| 42);
+    assert_eq!(*value, 42);
+
+    skip_list.entry("a").and_modify(|v| *v *= 10).or_insert(0);
+    skip_list.entry("e").and_modify(|v| *v *= 10).or_insert(7);
+    assert_eq!(skip_list.get(&"a"), Some(&20));
+    assert_eq!(skip_list.get(&"e"), Some(&7));
+}
+
+#[test]
+fn test_entry_occupied() {
+    let mut skip_list = SkipList::new();
+    for i in 0..10 {
+        skip_list.insert(i, i * 10);
+    }
+
+    match skip_list.entry(3) {
+        Entry::Vacant(_) => unreachable!(),
+        Entry::Occupied(mut entry) => {
+            assert_eq!(entry.key(), &3);
+            assert_eq!(entry.get(), &30);
+            *entry.get_mut() = 300;
+            assert_eq!(entry.insert(333), 300);
+            assert_eq!(entry.get(), &333);
+        }
+    }
+    assert_eq!(skip_list.get(&3), Some(&333));
+    assert_eq!(skip_list.len(), 10);
+
+    match skip_list.entry(5) {
+        Entry::Vacant(_) => unreachable!(),
+        Entry::Occupied(entry) => {
+            *entry.into_mut() += 1;
+        }
+    }
+    assert_eq!(skip_list.get(&5), Some(&51));
+}
+
+#[test]
+fn test_entry_remove() {
+    let mut skip_list = SkipList::new();
+    for i in 0..10 {
+        skip_list.insert(i, i * 10);
+    }
+
+    match skip_list.entry(4) {
+        Entry::Vacant(_) => unreachable!(),
+        Entry::Occupied(entry) => assert_eq!(entry.remove(), 40),
+    }
+    assert_eq!(skip_list.len(), 9);
+    assert_eq!(skip_list.get(&4), None);
+
+    match skip_list.entry(7) {
+        Entry::Vacant(_) => unreachable!(),
+        Entry::Occupied(entry) => assert_eq!(entry.remove_entry(), (7, 70)),
+    }
+    assert_eq!(skip_list.len(), 8);
+
+    let keys: Vec<_> = skip_list.iter().map(|(&k, _)| k).collect();
+    assert_eq!(keys, vec![0, 1, 2, 3, 5, 6, 8, 9]);
+    for (idx, key) in keys.iter().enumerate() {
+        assert_eq!(skip_list.index(idx), Some((key, &(key * 10))));
+    }
+}
+
+#[test]
+fn test_entry_vacant() {
+    let mut skip_list = SkipList::new();
+    skip_list.insert(1, "one");
+    skip_list.insert(3, "three");
+
+    match skip_list.entry(2) {
+        Entry::Occupied(_) => unreachable!(),
+        Entry::Vacant(entry) => {
+            assert_eq!(entry.key(), &2);
+            assert_eq!(entry.into_key(), 2);
+        }
+    }
+    // into_key leaves the list untouched
+    assert_eq!(skip_list.len(), 2);
+    assert_eq!(skip_list.get(&2), None);
+
+    match skip_list.entry(2) {
+        Entry::Occupied(_) => unreachable!(),
+        Entry::Vacant(entry) => {
+            let value = entry.insert("two");
+            *value = "TWO";
+        }
+    }
+    assert_eq!(skip_list.len(), 3);
+    assert_eq!(skip_list.index(1), Some((&2, &"TWO")));
+}
+
+#[test]
+fn test_entry_random_operations_match_btreemap() {
+    use std::collections::BTreeMap;
+
+    let mut skip_list = SkipList::new();
+    let mut btree = BTreeMap::new();
+
+    for i in 0..500 {
+        let key = (i * 37) % 101;
+        if i % 3 == 0 {
+            if let Entry::Occupied(entry) = skip_list.entry(key) {
+                assert_eq!(Some(entry.remove()), btree.remove(&key));
+            }
+        } else {
+            *skip_list.entry(key).or_insert(0) += 1;
+            *btree.entry(key).or_insert(0) += 1;
+        }
+    }
+
+    let items: Vec<_> = skip_list.iter().map(|(&k, &v)| (k, v)).collect();
+    let expected: Vec<_> = btree.iter().map(|(&k, &v)| (k, v)).collect();
+    assert_eq!(items, expected);
+    for (idx, (key, value)) in expected.iter().enumerate() {
+        assert_eq!(skip_list.index(idx), Some((key, value)));
+    }
+}
+
+#[test]
+fn test_entry_rank() {
+    let mut skip_list = SkipList::new();
+    for i in (0..100).step_by(2) {
+        skip_list.insert(i, i);
+    }
+
+    for key in [-1, 0, 1, 49, 50, 98, 99, 150] {
+        let entry = skip_list.entry(key);
+        let rank = entry.rank();
+        let was_vacant = matches!(entry, Entry::Vacant(_));
+        match entry {
+            Entry::Occupied(entry) => assert_eq!(entry.rank(), rank),
+            Entry::Vacant(entry) => {
+                assert_eq!(entry.rank(), rank);
+                entry.insert(key);
+            }
+        }
+
+        assert_eq!(skip_list.rank(&key), Some(rank), "rank of {}", key);
+        assert_eq!(skip_list.index(rank), Some((&key, &key)));
+
+        // A second lookup now finds the entry occupied at the same rank
+        assert_eq!(skip_list.entry(key).rank(), rank);
+        if was_vacant {
+            skip_list.remove(&key);
+        }
+    }
+
+    assert_eq!(skip_list.rank(&1), None);
+    assert_eq!(skip_list.rank(&1000), None);
+    assert_eq!(SkipList::<i32, i32>::new().entry(5).rank(), 0);
+}
+
+#[test]
+fn test_entry_or_insert_with_key() {
+    let mut lengths = SkipList::new();
+    for name in ["ann", "bob", "charlotte", "ann"] {
+        let len = lengths.entry(name).or_insert_with_key(|name| name.len());
+        *len += 100;
+    }
+    assert_eq!(lengths.get(&"ann"), Some(&203));
+    assert_eq!(lengths.get(&"bob"), Some(&103));
+    assert_eq!(lengths.get(&"charlotte"), Some(&109));
+
+    // Occupied entries never call the closure
+    lengths
+        .entry("bob")
+        .or_insert_with_key(|_| unreachable!("bob is present"));
+
+    match lengths.entry("dave") {
+        Entry::Occupied(_) => unreachable!(),
+        Entry::Vacant(entry) => {
+            assert_eq!(*entry.insert_with_key(|name| name.len() * 2), 8);
+        }
+    }
+    assert_eq!(lengths.index(2), Some((&"charlotte", &109)));
+    assert_eq!(lengths.index(3), Some((&"dave", &8)));
+}
+
+#[test]
+fn test_count_up() {
+    let mut counts = SkipList::new();
+    for key in ['a', 'b', 'a', 'c', 'a'] {
+        counts.count_up(key);
+    }
+    let entries: Vec<_> = counts.iter().map(|(&k, &v)| (k, v)).collect();
+    assert_eq!(entries, vec![('a', 3u64), ('b', 1), ('c', 1)]);
+
+    // The updated count is returned and can be adjusted further
+    assert_eq!(*counts.count_up('b'), 2);
+    *counts.count_up('d') += 10;
+    assert_eq!(counts.get(&'d'), Some(&11));
+
+    let mut weights: SkipList<i32, f64> = SkipList::new();
+    weights.count_up(1);
+    weights.count_up(1);
+    assert_eq!(weights.get(&1), Some(&2.0));
+}
+
+#[test]
+fn test_index_entry() {
+    let mut skip_list = SkipList::new();
+    for i in (0..10).rev() {
+        skip_list.insert(i * 10, i.to_string());
+    }
+
+    let mut entry = skip_list.index_entry(3).unwrap();
+    assert_eq!((entry.key(), entry.get().as_str(), entry.rank()), (&30, "3", 3));
+    entry.get_mut().push('!');
+    assert_eq!(skip_list.index(3), Some((&30, &"3!".to_string())));
+
+    // Removing another entry shifts the later positions down
+    assert_eq!(skip_list.index_entry(1).unwrap().remove_entry(), (10, "1".to_string()));
+    assert_eq!(skip_list.index(2), Some((&30, &"3!".to_string())));
+    skip_list.assert_invariants();
+
+    // Both ends, into_mut and out of bounds
+    assert_eq!(skip_list.index_entry(0).unwrap().remove(), "0");
+    let last = skip_list.len() - 1;
+    *skip_list.index_entry(last).unwrap().into_mut() = "last".to_string();
+    assert_eq!(skip_list.get(&90), Some(&"last".to_string()));
+    assert!(skip_list.index_entry(skip_list.len()).is_none());
+    skip_list.assert_invariants();
+
+    // Removing every entry through the front
+    while let Some(entry) = skip_list.index_entry(0) {
+        entry.remove();
+    }
+    assert!(skip_list.is_empty());
+    skip_list.assert_invariants();
+}
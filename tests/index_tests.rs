@@ -95,4 +95,452 @@ fn test_index_consistency_with_iteration() {
     for (idx, &(expected_key, expected_value)) in iterated.iter().enumerate() {
         assert_eq!(skip_list.index(idx), Some((&expected_key, &expected_value)));
     }
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_count_le_and_count_lt() {
+    let mut skip_list = SkipList::new();
+
+    for i in (0..100).step_by(2) {
+        skip_list.insert(i, i);
+    }
+
+    for k in -5..105 {
+        let expected_lt = (0..100).step_by(2).filter(|&x| x < k).count();
+        let expected_le = (0..100).step_by(2).filter(|&x| x <= k).count();
+        assert_eq!(skip_list.count_lt(&k), expected_lt, "count_lt({})", k);
+        assert_eq!(skip_list.count_le(&k), expected_le, "count_le({})", k);
+        assert_eq!(
+            skip_list.count_lt(&k) + skip_list.contains_key(&k) as usize,
+            skip_list.count_le(&k)
+        );
+    }
+
+    let empty: SkipList<i32, i32> = SkipList::new();
+    assert_eq!(empty.count_lt(&0), 0);
+    assert_eq!(empty.count_le(&0), 0);
+}
+
+#[test]
+fn test_floor_and_ceiling_index() {
+    let mut skip_list = SkipList::new();
+    for i in (0..100).step_by(2) {
+        skip_list.insert(i, i);
+    }
+
+    // Edge positions
+    assert_eq!(skip_list.floor_index(&-1), None);
+    assert_eq!(skip_list.floor_index(&0), Some(0));
+    assert_eq!(skip_list.floor_index(&1), Some(0));
+    assert_eq!(skip_list.floor_index(&98), Some(49));
+    assert_eq!(skip_list.floor_index(&1000), Some(49));
+    assert_eq!(skip_list.ceiling_index(&-1), Some(0));
+    assert_eq!(skip_list.ceiling_index(&0), Some(0));
+    assert_eq!(skip_list.ceiling_index(&97), Some(49));
+    assert_eq!(skip_list.ceiling_index(&98), Some(49));
+    assert_eq!(skip_list.ceiling_index(&99), None);
+
+    for k in -5..105 {
+        let floor = (0..100).step_by(2).rev().find(|&x| x <= k);
+        let ceiling = (0..100).step_by(2).find(|&x| x >= k);
+        assert_eq!(
+            skip_list.floor_index(&k).map(|i| *skip_list.index(i).unwrap().0),
+            floor,
+            "floor_index({})",
+            k
+        );
+        assert_eq!(
+            skip_list.ceiling_index(&k).map(|i| *skip_list.index(i).unwrap().0),
+            ceiling,
+            "ceiling_index({})",
+            k
+        );
+    }
+
+    let empty: SkipList<i32, i32> = SkipList::new();
+    assert_eq!(empty.floor_index(&0), None);
+    assert_eq!(empty.ceiling_index(&0), None);
+}
+
+#[test]
+fn test_index_range_array_mut() {
+    let mut skip_list = SkipList::new();
+    for i in 0..20 {
+        skip_list.insert(i, i);
+    }
+
+    // Mutate a window of 4 values
+    let window = skip_list.index_range_array_mut::<4>(8).unwrap();
+    for value in window {
+        *value += 100;
+    }
+    for i in 0..20 {
+        let expected = if (8..12).contains(&i) { i + 100 } else { i };
+        assert_eq!(skip_list.index(i as usize), Some((&i, &expected)));
+    }
+
+    // Windows touching both ends
+    let [first] = skip_list.index_range_array_mut::<1>(0).unwrap();
+    assert_eq!(*first, 0);
+    let [a, b] = skip_list.index_range_array_mut::<2>(18).unwrap();
+    std::mem::swap(a, b);
+    assert_eq!(skip_list.index(18), Some((&18, &19)));
+    assert_eq!(skip_list.index(19), Some((&19, &18)));
+
+    // Out of range
+    assert!(skip_list.index_range_array_mut::<2>(19).is_none());
+    assert!(skip_list.index_range_array_mut::<21>(0).is_none());
+    assert!(skip_list.index_range_array_mut::<1>(usize::MAX).is_none());
+    assert!(skip_list.index_range_array_mut::<0>(20).is_some());
+    assert!(skip_list.index_range_array_mut::<0>(21).is_none());
+}
+
+#[test]
+fn test_select_by() {
+    let mut skip_list = SkipList::new();
+    // Values happen to be sorted along with the keys
+    for i in 0..100 {
+        skip_list.insert(i, i * 3 + 1);
+    }
+
+    for i in 0..100 {
+        let target = i * 3 + 1;
+        assert_eq!(
+            skip_list.select_by(|_, v| v.cmp(&target)),
+            Some((i as usize, &i, &target))
+        );
+        assert_eq!(skip_list.select_by(|_, v| v.cmp(&(target + 1))), None);
+    }
+    assert_eq!(skip_list.select_by(|_, v| v.cmp(&0)), None);
+    assert_eq!(skip_list.select_by(|_, v| v.cmp(&1000)), None);
+
+    // Comparing on keys matches rank
+    assert_eq!(skip_list.select_by(|k, _| k.cmp(&42)).map(|(rank, _, _)| rank), Some(42));
+
+    // Several equal entries: the first one wins
+    assert_eq!(
+        skip_list.select_by(|k, _| (k / 10).cmp(&5)),
+        Some((50, &50, &151))
+    );
+
+    let empty: SkipList<i32, i32> = SkipList::new();
+    assert_eq!(empty.select_by(|k, _| k.cmp(&0)), None);
+}
+
+#[test]
+fn test_remove_index_with_neighbor() {
+    let mut skip_list = SkipList::new();
+    for i in 0..5 {
+        skip_list.insert(i, i * 10);
+    }
+
+    // Removing the middle element shifts its successor into the slot
+    assert_eq!(skip_list.remove_index_with_neighbor(2), Some((2, 20, Some(2))));
+    assert_eq!(skip_list.index(2), Some((&3, &30)));
+    assert_eq!(skip_list.len(), 4);
+
+    // Removing the last element leaves the slot empty
+    assert_eq!(skip_list.remove_index_with_neighbor(3), Some((4, 40, None)));
+    assert_eq!(skip_list.remove_index_with_neighbor(3), None);
+
+    assert_eq!(skip_list.remove_index_with_neighbor(0), Some((0, 0, Some(0))));
+    assert_eq!(skip_list.index(0), Some((&1, &10)));
+
+    let keys: Vec<_> = skip_list.iter().map(|(&k, _)| k).collect();
+    assert_eq!(keys, vec![1, 3]);
+
+    // Draining by position keeps everything consistent
+    let mut skip_list = SkipList::new();
+    for i in 0..200 {
+        skip_list.insert(i, i);
+    }
+    let mut expected: Vec<i32> = (0..200).collect();
+    let mut index = 7;
+    while !skip_list.is_empty() {
+        index %= skip_list.len();
+        let (key, _, neighbor) = skip_list.remove_index_with_neighbor(index).unwrap();
+        assert_eq!(key, expected.remove(index));
+        assert_eq!(neighbor, (index < expected.len()).then_some(index));
+        assert_eq!(skip_list.len(), expected.len());
+        index += 13;
+    }
+}
+
+#[test]
+fn test_first_missing_in() {
+    let mut ids = SkipList::new();
+    for k in [0u64, 1, 2, 4] {
+        ids.insert(k, ());
+    }
+
+    assert_eq!(ids.first_missing_in(0..10), Some(3));
+    assert_eq!(ids.first_missing_in(3..10), Some(3));
+    assert_eq!(ids.first_missing_in(4..10), Some(5));
+    assert_eq!(ids.first_missing_in(1..3), None);
+    assert_eq!(ids.first_missing_in(4..5), None);
+    assert_eq!(ids.first_missing_in(5..5), None);
+    assert_eq!(ids.first_missing_in(100..200), Some(100));
+
+    // Dense prefix allocation
+    let mut ids = SkipList::new();
+    for _ in 0..500 {
+        let id = ids.first_missing_in(0..u64::MAX).unwrap();
+        assert_eq!(id, ids.len() as u64);
+        ids.insert(id, ());
+    }
+    assert_eq!(ids.first_missing_in(0..500), None);
+
+    // Punch holes and compare against a linear scan
+    for k in [3u64, 250, 251, 499, 7] {
+        ids.remove(&k);
+    }
+    for start in [0u64, 3, 4, 8, 100, 250, 252, 498, 499, 500, 600] {
+        for end in [start, start + 1, start + 5, 1000] {
+            let expected = (start..end).find(|k| !ids.contains_key(k));
+            assert_eq!(ids.first_missing_in(start..end), expected, "{}..{}", start, end);
+        }
+    }
+
+    // Keys near the top of the integer range
+    let mut ids = SkipList::new();
+    for k in [u64::MAX - 2, u64::MAX - 1] {
+        ids.insert(k, ());
+    }
+    assert_eq!(ids.first_missing_in(u64::MAX - 2..u64::MAX), None);
+    assert_eq!(ids.first_missing_in(u64::MAX - 3..u64::MAX), Some(u64::MAX - 3));
+
+    assert_eq!(SkipList::<u64, ()>::new().first_missing_in(0..1), Some(0));
+}
+
+#[test]
+fn test_set_index() {
+    let mut skip_list = SkipList::new();
+    for i in 0..5 {
+        skip_list.insert(i, i * 10);
+    }
+
+    assert_eq!(skip_list.set_index(2, 99), Some(20));
+    assert_eq!(skip_list.index(2), Some((&2, &99)));
+    assert_eq!(skip_list.get(&2), Some(&99));
+    assert_eq!(skip_list.len(), 5);
+
+    assert_eq!(skip_list.set_index(0, -1), Some(0));
+    assert_eq!(skip_list.set_index(4, -4), Some(40));
+    assert_eq!(skip_list.set_index(5, 7), None);
+
+    let values: Vec<_> = skip_list.iter().map(|(_, &v)| v).collect();
+    assert_eq!(values, vec![-1, 10, 99, 30, -4]);
+}
+
+#[test]
+fn test_key_bounds() {
+    let mut skip_list = SkipList::new();
+    assert_eq!(skip_list.key_bounds(), None);
+
+    for i in (0..100).rev() {
+        skip_list.insert(i * 3 - 50, i);
+    }
+    assert_eq!(skip_list.key_bounds(), Some((&-50, &247)));
+
+    skip_list.remove(&-50);
+    skip_list.remove(&247);
+    assert_eq!(skip_list.key_bounds(), Some((&-47, &244)));
+
+    let mut single = SkipList::new();
+    single.insert(7, ());
+    assert_eq!(single.key_bounds(), Some((&7, &7)));
+    single.remove(&7);
+    assert_eq!(single.key_bounds(), None);
+}
+
+#[test]
+fn test_find_vs_lower_bound_index() {
+    let mut skip_list = SkipList::new();
+    for i in (0..100).step_by(2) {
+        skip_list.insert(i, i);
+    }
+
+    // Present keys agree
+    assert_eq!(skip_list.find(&0), Some(0));
+    assert_eq!(skip_list.find(&42), Some(21));
+    assert_eq!(skip_list.lower_bound_index(&42), 21);
+
+    // Missing keys have no position, but still an insertion point
+    assert_eq!(skip_list.find(&43), None);
+    assert_eq!(skip_list.lower_bound_index(&43), 22);
+    assert_eq!(skip_list.find(&-1), None);
+    assert_eq!(skip_list.lower_bound_index(&-1), 0);
+    assert_eq!(skip_list.find(&1000), None);
+    assert_eq!(skip_list.lower_bound_index(&1000), 50);
+
+    for k in -5..105 {
+        assert_eq!(skip_list.find(&k), skip_list.rank(&k));
+        assert_eq!(
+            skip_list.find(&k).is_some(),
+            skip_list.contains_key(&k),
+            "key {}",
+            k
+        );
+    }
+}
+
+#[test]
+fn test_search_sorted_value() {
+    let skip_list = SkipList::from_sorted((0..100).map(|i| (i * 3, i * 3)));
+    let values: Vec<i32> = (0..100).map(|i| i * 3).collect();
+
+    // Same answers as slice::binary_search on the values
+    for target in -2..305 {
+        assert_eq!(
+            skip_list.search_sorted_value(&target),
+            values.binary_search(&target),
+            "value {}",
+            target
+        );
+    }
+    assert_eq!(skip_list.search_sorted_value(&0), Ok(0));
+    assert_eq!(skip_list.search_sorted_value(&297), Ok(99));
+    assert_eq!(skip_list.search_sorted_value(&1000), Err(100));
+
+    // Values monotonic in key order but unrelated to the keys
+    let names = SkipList::from_sorted([(1, "ant"), (5, "bee"), (9, "cat")]);
+    assert_eq!(names.search_sorted_value("bee"), Ok(1));
+    assert_eq!(names.search_sorted_value("bat"), Err(1));
+
+    assert_eq!(SkipList::<i32, i32>::new().search_sorted_value(&1), Err(0));
+}
+
+#[test]
+fn test_count_with_prefix() {
+    let words = [
+        "a", "b", "ba", "baa", "bab", "bad", "bag", "banana", "bb", "c", "\u{10FFFF}", "\u{10FFFF}a",
+    ];
+    let mut skip_list = SkipList::new();
+    for word in words {
+        skip_list.insert(word.to_string(), word.len());
+    }
+
+    assert_eq!(skip_list.count_with_prefix("ba"), 6);
+    assert_eq!(skip_list.count_with_prefix("b"), 8);
+    assert_eq!(skip_list.count_with_prefix("bag"), 1);
+    assert_eq!(skip_list.count_with_prefix("bah"), 0);
+    assert_eq!(skip_list.count_with_prefix("zzz"), 0);
+    assert_eq!(skip_list.count_with_prefix(""), words.len());
+
+    // The last char cannot be incremented, which must not matter
+    assert_eq!(skip_list.count_with_prefix("\u{10FFFF}"), 2);
+
+    for prefix in ["", "a", "b", "ba", "bad", "bb", "c", "d"] {
+        let expected = words.iter().filter(|w| w.starts_with(prefix)).count();
+        assert_eq!(skip_list.count_with_prefix(prefix), expected, "prefix {:?}", prefix);
+    }
+
+    assert_eq!(SkipList::<String, ()>::new().count_with_prefix("ba"), 0);
+}
+
+#[test]
+fn test_nearest() {
+    let skip_list = SkipList::from_sorted([(1i32, 'a'), (5, 'b'), (9, 'c')]);
+
+    assert_eq!(skip_list.nearest(&6), Some((&5, &'b')));
+    assert_eq!(skip_list.nearest(&5), Some((&5, &'b')));
+    assert_eq!(skip_list.nearest(&8), Some((&9, &'c')));
+    // Ties go to the smaller key
+    assert_eq!(skip_list.nearest(&3), Some((&1, &'a')));
+    assert_eq!(skip_list.nearest(&7), Some((&5, &'b')));
+    // Beyond either end
+    assert_eq!(skip_list.nearest(&-100), Some((&1, &'a')));
+    assert_eq!(skip_list.nearest(&100), Some((&9, &'c')));
+
+    // Unsigned keys never subtract below zero
+    let unsigned = SkipList::from_sorted([(10u8, ()), (200, ())]);
+    assert_eq!(unsigned.nearest(&0), Some((&10, &())));
+    assert_eq!(unsigned.nearest(&255), Some((&200, &())));
+    assert_eq!(unsigned.nearest(&106), Some((&200, &())));
+
+    // A custom distance: closeness modulo 10
+    let wrapped = skip_list.nearest_by(&10, |a, b| (a % 10 - b % 10).abs());
+    assert_eq!(wrapped, Some((&9, &'c')));
+    let floats = SkipList::from_sorted([(0i32, ()), (10, ())]);
+    assert_eq!(floats.nearest_by(&6, |a, b| (*a as f64 - *b as f64).abs()), Some((&10, &())));
+
+    assert_eq!(SkipList::<i32, ()>::new().nearest(&1), None);
+
+    // Agrees with a linear scan
+    let skip_list = SkipList::from_sorted((0i32..50).map(|i| (i * i, i)));
+    for key in -5i32..2600 {
+        let expected = (0..50)
+            .map(|i| i * i)
+            .min_by_key(|&k| (k - key).abs())
+            .unwrap();
+        assert_eq!(skip_list.nearest(&key).map(|(&k, _)| k), Some(expected), "key {}", key);
+    }
+}
+
+#[test]
+fn test_build_position_index() {
+    let mut skip_list = SkipList::new();
+    for i in (0..300).rev() {
+        skip_list.insert(i * 3, ());
+    }
+    for i in (0..300).step_by(4) {
+        skip_list.remove(&(i * 3));
+    }
+
+    let index = skip_list.build_position_index();
+    assert_eq!(index.len(), skip_list.len());
+    for (i, &key) in index.iter().enumerate() {
+        assert_eq!(key, skip_list.index(i).unwrap().0);
+        assert_eq!(skip_list.rank(key), Some(i));
+    }
+
+    assert!(SkipList::<i32, ()>::new().build_position_index().is_empty());
+}
+
+#[test]
+fn test_sample() {
+    use rand::{SeedableRng, rngs::StdRng};
+
+    let mut rng = StdRng::seed_from_u64(11);
+    let mut skip_list = SkipList::new();
+    assert_eq!(skip_list.sample(&mut rng), None);
+    assert!(skip_list.sample_many(3, &mut rng).is_empty());
+
+    for i in (0..20).rev() {
+        skip_list.insert(i, i * 100);
+    }
+
+    // Each of the 20 positions is hit about 1/20 of the time
+    let draws = 40_000;
+    let mut counts = [0usize; 20];
+    for _ in 0..draws {
+        let (&k, &v) = skip_list.sample(&mut rng).unwrap();
+        assert_eq!(v, k * 100);
+        counts[k as usize] += 1;
+    }
+    let expected = draws / 20;
+    for (position, &count) in counts.iter().enumerate() {
+        assert!(
+            count.abs_diff(expected) < expected / 10,
+            "position {} drawn {} times, expected about {}",
+            position,
+            count,
+            expected
+        );
+    }
+
+    // Without replacement, also uniform over positions
+    let mut counts = [0usize; 20];
+    for _ in 0..4_000 {
+        let picked = skip_list.sample_many(5, &mut rng);
+        assert_eq!(picked.len(), 5);
+        assert!(picked.windows(2).all(|pair| pair[0].0 < pair[1].0));
+        for (&k, _) in picked {
+            counts[k as usize] += 1;
+        }
+    }
+    assert!(counts.iter().all(|&count| count.abs_diff(1_000) < 100));
+
+    let all = skip_list.sample_many(50, &mut rng);
+    assert!(all.into_iter().eq(skip_list.iter()));
+}
@@ -1,5 +1,5 @@
-use skiplist::SkipList;
-use std::collections::BTreeSet;
+use skiplist::{SkipList, SkipListBuilder};
+use std::collections::{BTreeMap, BTreeSet};
 
 #[test]
 fn test_basic_operations() {
@@ -161,4 +161,504 @@ fn test_negative_numbers() {
     // Should be in numerical order
     let keys: Vec<_> = (&skip_list).into_iter().map(|(&k, _)| k).collect();
     assert_eq!(keys, vec![-10, -5, -3, -1, 0, 3, 7]);
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_bulk_remove() {
+    let mut skip_list = SkipList::new();
+    for i in 0..100 {
+        skip_list.insert(i, i * 10);
+    }
+
+    // Mix of present and absent keys
+    let keys = [-5, 0, 3, 3, 10, 50, 51, 99, 150];
+    assert_eq!(skip_list.bulk_remove(&keys), 6);
+    assert_eq!(skip_list.len(), 94);
+
+    let expected: Vec<_> = (0..100).filter(|k| ![0, 3, 10, 50, 51, 99].contains(k)).collect();
+    let actual: Vec<_> = skip_list.iter().map(|(&k, _)| k).collect();
+    assert_eq!(actual, expected);
+    for (idx, key) in expected.iter().enumerate() {
+        assert_eq!(skip_list.index(idx), Some((key, &(key * 10))));
+    }
+
+    // Removing everything empties the list
+    assert_eq!(skip_list.bulk_remove(&expected), 94);
+    assert!(skip_list.is_empty());
+    assert_eq!(skip_list.bulk_remove::<i32>(&[]), 0);
+}
+
+#[test]
+fn test_bulk_remove_matches_remove_loop() {
+    let mut bulk = SkipList::new();
+    let mut single = SkipList::new();
+    for i in 0..2000 {
+        bulk.insert(i, i);
+        single.insert(i, i);
+    }
+
+    let keys: Vec<i32> = (0..2000).filter(|k| k % 7 == 0 || k % 11 == 0).collect();
+    let removed = bulk.bulk_remove(&keys);
+    let expected_removed = keys.iter().filter(|k| single.remove(*k).is_some()).count();
+
+    assert_eq!(removed, expected_removed);
+    assert_eq!(bulk.iter().collect::<Vec<_>>(), single.iter().collect::<Vec<_>>());
+}
+
+#[test]
+fn test_clone_and_snapshot() {
+    let mut skip_list = SkipList::new();
+    for i in 0..50 {
+        skip_list.insert(i, i.to_string());
+    }
+
+    let cloned = skip_list.clone();
+    // Exact-structure clone renders the same tower
+    assert_eq!(cloned.format_tower(), skip_list.format_tower());
+
+    let snapshot = skip_list.snapshot();
+
+    // Mutating the original leaves the snapshot untouched
+    skip_list.insert(100, "new".to_string());
+    skip_list.remove(&10);
+    *skip_list.get_mut(&20).unwrap() = "changed".to_string();
+
+    assert_eq!(snapshot.len(), 50);
+    assert_eq!(snapshot.get(&100), None);
+    assert_eq!(snapshot.get(&10), Some(&"10".to_string()));
+    assert_eq!(snapshot.get(&20), Some(&"20".to_string()));
+    for i in 0..50 {
+        assert_eq!(snapshot.index(i as usize), Some((&i, &i.to_string())));
+    }
+
+    assert_eq!(skip_list.len(), 50);
+    assert_eq!(skip_list.get(&20), Some(&"changed".to_string()));
+}
+
+#[test]
+fn test_extend() {
+    let mut skip_list = SkipList::from_sorted([(1, 'a'), (2, 'b')]);
+
+    assert_eq!(skip_list.extend_counted([(2, 'x'), (3, 'y')]), 1);
+    assert_eq!(skip_list.get(&2), Some(&'x'));
+    assert_eq!(skip_list.len(), 3);
+
+    // Duplicates within the input count once, the last value wins
+    assert_eq!(skip_list.extend_counted([(5, 'p'), (4, 'q'), (5, 'r')]), 2);
+    assert_eq!(skip_list.get(&5), Some(&'r'));
+    assert_eq!(skip_list.extend_counted(std::iter::empty()), 0);
+
+    skip_list.extend((6..10).map(|k| (k, 'z')));
+    assert_eq!(skip_list.len(), 9);
+    assert!(skip_list.iter().map(|(&k, _)| k).eq(1..10));
+}
+
+#[test]
+fn test_for_each_value_mut() {
+    let mut skip_list = SkipList::new();
+    for i in (0..100).rev() {
+        let mut value = String::with_capacity(256);
+        value.push_str(&i.to_string());
+        skip_list.insert(i, value);
+    }
+
+    let mut visited = Vec::new();
+    skip_list.for_each_value_mut(|value| {
+        visited.push(value.clone());
+        value.push('!');
+        value.shrink_to_fit();
+    });
+
+    // Visited in key order, and every change persisted
+    assert_eq!(visited, (0..100).map(|i| i.to_string()).collect::<Vec<_>>());
+    for (i, value) in skip_list.iter() {
+        assert_eq!(value, &format!("{}!", i));
+        assert!(value.capacity() < 256);
+    }
+
+    let mut empty: SkipList<i32, String> = SkipList::new();
+    empty.for_each_value_mut(|_| unreachable!());
+}
+
+#[test]
+fn test_swap() {
+    let mut front = SkipList::from_sorted((0..100).map(|i| (i, i)));
+    let mut back = SkipList::new();
+    for i in (500..520).rev() {
+        back.insert(i, -i);
+    }
+
+    front.swap(&mut back);
+    assert_eq!(front.len(), 20);
+    assert!(front.iter().map(|(&k, &v)| (k, v)).eq((500..520).map(|i| (i, -i))));
+    assert_eq!(back.len(), 100);
+    assert!(back.iter().map(|(&k, &v)| (k, v)).eq((0..100).map(|i| (i, i))));
+    front.assert_invariants();
+    back.assert_invariants();
+    #[cfg(feature = "test-utils")]
+    assert!(front.verify_spans() && back.verify_spans());
+
+    // Both stay fully usable, including positional access
+    front.insert(510, 0);
+    back.remove(&50);
+    assert_eq!(front.index(10), Some((&510, &0)));
+    assert_eq!(back.index(50), Some((&51, &51)));
+
+    let mut empty = SkipList::new();
+    back.swap(&mut empty);
+    assert!(back.is_empty());
+    assert_eq!(empty.len(), 99);
+    back.assert_invariants();
+}
+
+#[test]
+fn test_try_reserve_levels() {
+    let mut skip_list = SkipList::from_sorted((0..50).map(|i| (i, i)));
+    let before = skip_list.stats();
+
+    skip_list.try_reserve_levels(64).unwrap();
+    let after = skip_list.stats();
+    assert!(after.memory_bytes > before.memory_bytes);
+    assert_eq!(after.level, before.level);
+    assert_eq!(after.len, before.len);
+    assert!(skip_list.iter().map(|(&k, _)| k).eq(0..50));
+    skip_list.assert_invariants();
+
+    // Already reserved, nothing to allocate
+    skip_list.try_reserve_levels(8).unwrap();
+    assert_eq!(skip_list.stats().memory_bytes, after.memory_bytes);
+
+    // An impossible request fails without touching the list
+    assert!(skip_list.try_reserve_levels(usize::MAX).is_err());
+    assert_eq!(skip_list.stats(), after);
+    skip_list.insert(50, 50);
+    skip_list.assert_invariants();
+}
+
+#[test]
+fn test_get_cloned() {
+    let mut skip_list = SkipList::new();
+    skip_list.insert("a", vec![1, 2, 3]);
+    skip_list.insert("b", vec![4]);
+
+    let mut owned = skip_list.get_cloned("a").unwrap();
+    assert_eq!(skip_list.remove("a"), Some(vec![1, 2, 3]));
+
+    // The copy outlives the entry and is independent of the list
+    owned.push(4);
+    assert_eq!(owned, vec![1, 2, 3, 4]);
+    assert_eq!(skip_list.get_cloned("a"), None);
+    assert_eq!(skip_list.get_cloned("b"), Some(vec![4]));
+}
+
+#[test]
+fn test_get_pair_mut() {
+    let mut skip_list = SkipList::new();
+    for i in 0..100 {
+        skip_list.insert(i, i * 10);
+    }
+
+    // Swap two values through the returned references
+    let (a, b) = skip_list.get_pair_mut(&3, &97).unwrap();
+    std::mem::swap(a, b);
+    assert_eq!(skip_list.get(&3), Some(&970));
+    assert_eq!(skip_list.get(&97), Some(&30));
+
+    // References come back in argument order
+    let (a, b) = skip_list.get_pair_mut(&50, &10).unwrap();
+    assert_eq!((*a, *b), (500, 100));
+    *a += 1;
+    *b += 2;
+    assert_eq!(skip_list.get(&50), Some(&501));
+    assert_eq!(skip_list.get(&10), Some(&102));
+
+    // Adjacent keys and both ends of the list
+    let (a, b) = skip_list.get_pair_mut(&0, &1).unwrap();
+    assert_eq!((*a, *b), (0, 10));
+    let (a, b) = skip_list.get_pair_mut(&0, &99).unwrap();
+    assert_eq!((*a, *b), (0, 990));
+
+    // Equal or missing keys
+    assert!(skip_list.get_pair_mut(&5, &5).is_none());
+    assert!(skip_list.get_pair_mut(&5, &100).is_none());
+    assert!(skip_list.get_pair_mut(&-1, &5).is_none());
+    assert!(SkipList::<i32, i32>::new().get_pair_mut(&1, &2).is_none());
+
+    // Every pair agrees with separate lookups
+    for a in (0..100).step_by(7) {
+        for b in (0..100).step_by(11) {
+            let expected = (skip_list.get(&a).copied(), skip_list.get(&b).copied());
+            match skip_list.get_pair_mut(&a, &b) {
+                Some((x, y)) => assert_eq!((Some(*x), Some(*y)), expected),
+                None => assert_eq!(a, b),
+            }
+        }
+    }
+}
+
+#[test]
+fn test_remove_first_and_last_n() {
+    let mut skip_list = SkipList::new();
+    for i in 0..20 {
+        skip_list.insert(i, i.to_string());
+    }
+
+    assert_eq!(skip_list.remove_first_n(5), 5);
+    assert_eq!(skip_list.remove_last_n(5), 5);
+    assert_eq!(skip_list.len(), 10);
+    let keys: Vec<_> = skip_list.iter().map(|(&k, _)| k).collect();
+    assert_eq!(keys, (5..15).collect::<Vec<_>>());
+    assert_eq!(skip_list.index(0), Some((&5, &"5".to_string())));
+    assert_eq!(skip_list.index(9), Some((&14, &"14".to_string())));
+    assert_eq!(skip_list.get(&4), None);
+    assert_eq!(skip_list.get(&15), None);
+
+    // The list keeps working after trimming
+    skip_list.insert(0, "0".to_string());
+    skip_list.insert(19, "19".to_string());
+    assert_eq!(skip_list.len(), 12);
+    assert_eq!(skip_list.index(11), Some((&19, &"19".to_string())));
+
+    assert_eq!(skip_list.remove_first_n(0), 0);
+    assert_eq!(skip_list.remove_last_n(0), 0);
+
+    // Counts past the length are clamped
+    assert_eq!(skip_list.remove_last_n(100), 12);
+    assert!(skip_list.is_empty());
+    assert_eq!(skip_list.remove_first_n(1), 0);
+
+    skip_list.insert(1, "1".to_string());
+    assert_eq!(skip_list.remove_first_n(100), 1);
+    assert!(skip_list.is_empty());
+}
+
+#[test]
+fn test_display_formats() {
+    // A single level keeps the tower deterministic
+    let mut skip_list = SkipListBuilder::new().max_level(0).build().unwrap();
+    skip_list.insert(2, "b");
+    skip_list.insert(1, "a");
+
+    assert_eq!(format!("{}", skip_list), r#"{1: "a", 2: "b"}"#);
+    assert_eq!(skip_list.format_compact(), r#"{1: "a", 2: "b"}"#);
+    assert_eq!(
+        skip_list.format_tower(),
+        concat!(
+            "L0 |HEAD -> (1: \"a\") -> (2: \"b\") -> TAIL\n",
+            "   |(1)     (1)         (1)             \n",
+        )
+    );
+
+    let empty: SkipList<i32, i32> = SkipList::new();
+    assert_eq!(empty.format_compact(), "{}");
+    assert_eq!(empty.format_tower(), "L0 |HEAD -> TAIL\n   |(1)         \n");
+}
+
+#[test]
+fn test_debug_head_tail() {
+    let letters: Vec<char> = ('a'..='z').collect();
+    let skip_list = SkipList::from_sorted((1..=100).map(|i| (i, letters[(i - 1) % 26])));
+
+    assert_eq!(
+        skip_list.debug_head_tail(2),
+        "{1: 'a', 2: 'b', ..., 99: 'u', 100: 'v'}"
+    );
+    assert_eq!(skip_list.debug_head_tail(1), "{1: 'a', ..., 100: 'v'}");
+    assert_eq!(skip_list.debug_head_tail(0), "{...}");
+
+    // Short lists are shown in full
+    assert_eq!(skip_list.debug_head_tail(50), skip_list.format_compact());
+    assert_eq!(skip_list.debug_head_tail(usize::MAX), skip_list.format_compact());
+    let small = SkipList::from_sorted([(1, 'x'), (2, 'y'), (3, 'z')]);
+    assert_eq!(small.debug_head_tail(1), "{1: 'x', ..., 3: 'z'}");
+    assert_eq!(small.debug_head_tail(2), "{1: 'x', 2: 'y', 3: 'z'}");
+    assert_eq!(SkipList::<i32, i32>::new().debug_head_tail(0), "{}");
+}
+
+#[test]
+fn test_union() {
+    let mut a = SkipList::new();
+    a.insert(1, "a".to_string());
+    a.insert(3, "c".to_string());
+    let mut b = SkipList::new();
+    b.insert(2, "b".to_string());
+    b.insert(3, "d".to_string());
+
+    let union = a.union(&b, |x, y| format!("{}{}", x, y));
+    let entries: Vec<_> = union.iter().map(|(&k, v)| (k, v.as_str())).collect();
+    assert_eq!(entries, vec![(1, "a"), (2, "b"), (3, "cd")]);
+
+    // Inputs are left untouched
+    assert_eq!(a.len(), 2);
+    assert_eq!(b.get(&3), Some(&"d".to_string()));
+
+    // Larger interleaved lists, checked against a BTreeSet of keys
+    let mut left = SkipList::new();
+    let mut right = SkipList::new();
+    for i in 0..300 {
+        if i % 2 == 0 {
+            left.insert(i, 1);
+        }
+        if i % 3 == 0 {
+            right.insert(i, 10);
+        }
+    }
+    let union = left.union(&right, |x, y| x + y);
+    let keys: BTreeSet<_> = (0..300).filter(|i| i % 2 == 0 || i % 3 == 0).collect();
+    assert_eq!(union.len(), keys.len());
+    for (&k, &v) in union.iter() {
+        let expected = (k % 2 == 0) as i32 + 10 * (k % 3 == 0) as i32;
+        assert_eq!(v, expected, "key {}", k);
+    }
+    assert_eq!(union.index(keys.len() - 1), Some((&298, &1)));
+
+    let empty = SkipList::new();
+    assert_eq!(left.union(&empty, |x, _| *x).len(), left.len());
+    assert_eq!(empty.union(&right, |x, _| *x).len(), right.len());
+}
+
+#[test]
+fn test_get_batch() {
+    let mut skip_list = SkipList::new();
+    for i in 0..500 {
+        skip_list.insert(i * 2, i);
+    }
+
+    // Sorted input, with gaps, duplicates and keys past either end
+    let sorted = [-5, 0, 0, 1, 2, 3, 100, 101, 500, 998, 998, 999, 2000];
+    let expected: Vec<_> = sorted.iter().map(|k| skip_list.get(k)).collect();
+    assert_eq!(skip_list.get_batch(&sorted), expected);
+    assert_eq!(
+        skip_list.get_batch(&[0, 2, 998]),
+        vec![Some(&0), Some(&1), Some(&499)]
+    );
+
+    // Unsorted input falls back to per-key lookups
+    let unsorted = [998, 0, 7, 500, -1, 2];
+    let expected: Vec<_> = unsorted.iter().map(|k| skip_list.get(k)).collect();
+    assert_eq!(skip_list.get_batch(&unsorted), expected);
+
+    // Every key, in order
+    let all: Vec<i32> = (0..1000).collect();
+    let found = skip_list.get_batch(&all);
+    for (k, v) in all.iter().zip(found) {
+        assert_eq!(v, skip_list.get(k), "key {}", k);
+    }
+
+    assert!(skip_list.get_batch(&[] as &[i32]).is_empty());
+    assert_eq!(SkipList::<i32, i32>::new().get_batch(&[1, 2]), vec![None, None]);
+}
+
+#[test]
+fn test_btreemap_round_trip() {
+    let map: BTreeMap<i32, String> =
+        (0..1000).map(|i| (i * 7 % 1000, format!("v{}", i))).collect();
+
+    let skip_list = SkipList::from(map.clone());
+    assert_eq!(skip_list.len(), 1000);
+    for (k, v) in &map {
+        assert_eq!(skip_list.get(k), Some(v));
+    }
+    assert!(skip_list.iter().map(|(k, _)| k).eq(map.keys()));
+
+    let back: BTreeMap<_, _> = skip_list.into();
+    assert_eq!(back, map);
+
+    let empty = SkipList::<i32, i32>::from(BTreeMap::new());
+    assert!(empty.is_empty());
+    assert!(BTreeMap::from(empty).is_empty());
+}
+
+#[test]
+fn test_drain_while() {
+    let mut skip_list = SkipList::from_sorted([(1, 'a'), (3, 'b'), (5, 'c'), (7, 'd')]);
+    assert_eq!(skip_list.drain_while(|&k, _| k < 5), vec![(1, 'a'), (3, 'b')]);
+    assert_eq!(skip_list.len(), 2);
+    assert_eq!(skip_list.index(0), Some((&5, &'c')));
+    assert_eq!(skip_list.index(1), Some((&7, &'d')));
+
+    // Nothing matches: the list is untouched
+    assert!(skip_list.drain_while(|&k, _| k < 5).is_empty());
+    assert_eq!(skip_list.len(), 2);
+
+    // f stops being called at the first false
+    let mut calls = 0;
+    let mut skip_list = SkipList::from_sorted((0..1000).map(|i| (i, i * 2)));
+    let drained = skip_list.drain_while(|_, &v| {
+        calls += 1;
+        v < 600
+    });
+    assert_eq!(calls, 301);
+    assert_eq!(drained.len(), 300);
+    assert!(drained.iter().enumerate().all(|(i, &(k, v))| k == i && v == i * 2));
+    for i in 0..700 {
+        assert_eq!(skip_list.index(i), Some((&(i + 300), &((i + 300) * 2))));
+    }
+
+    // The list keeps working and can be drained completely
+    skip_list.insert(5, 5);
+    assert_eq!(skip_list.index(0), Some((&5, &5)));
+    assert_eq!(skip_list.drain_while(|_, _| true).len(), 701);
+    assert!(skip_list.is_empty());
+    skip_list.insert(1, 1);
+    assert_eq!(skip_list.index(0), Some((&1, &1)));
+}
+
+#[test]
+fn test_remap_keys() {
+    let mut skip_list = SkipList::from_sorted((0..200).map(|i| (i, i * 10)));
+
+    // Order-preserving shift: lookups work through the new keys only
+    skip_list.remap_keys(|k| k + 100);
+    assert_eq!(skip_list.len(), 200);
+    for i in 0..200 {
+        assert_eq!(skip_list.get(&(i + 100)), Some(&(i * 10)));
+        assert_eq!(skip_list.index(i as usize), Some((&(i + 100), &(i * 10))));
+    }
+    assert_eq!(skip_list.get(&50), None);
+    assert!(skip_list.iter().map(|(&k, _)| k).eq(100..300));
+
+    // Order-reversing map forces a rebuild
+    skip_list.remap_keys(|k| -k);
+    assert!(skip_list.iter().map(|(&k, _)| k).eq((-299..=-100).collect::<Vec<_>>()));
+    assert_eq!(skip_list.get(&-100), Some(&0));
+    assert_eq!(skip_list.index(0), Some((&-299, &1990)));
+
+    // Colliding keys: the entry that came later in the old order wins
+    skip_list.remap_keys(|k| k / 100);
+    let entries: Vec<_> = skip_list.iter().map(|(&k, &v)| (k, v)).collect();
+    assert_eq!(entries, vec![(-2, 1000), (-1, 0)]);
+
+    skip_list.insert(7, 7);
+    assert_eq!(skip_list.index(2), Some((&7, &7)));
+
+    let mut empty = SkipList::<i32, i32>::new();
+    empty.remap_keys(|k| k + 1);
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn test_format_tower_truncates_long_lists() {
+    let skip_list = SkipList::from_sorted((0..1000).map(|i| (i, i)));
+    let tower = skip_list.format_tower();
+
+    // One line per level for nodes and spans, one between levels for the
+    // connectors, each only 17 columns wide
+    let lines: Vec<_> = tower.lines().collect();
+    assert_eq!(lines.len(), 3 * skip_list.stats().height - 1);
+    assert!(lines.iter().all(|line| line.len() < 400));
+    assert!(tower.len() < 20_000);
+
+    // The first and last 8 entries are drawn, around an ellipsis column
+    let bottom = lines[lines.len() - 2];
+    assert!(bottom.starts_with("L0 |HEAD -> (0: 0) -> (1: 1)"));
+    assert!(bottom.contains("(7: 7)    ... -> (992: 992)"));
+    assert!(bottom.ends_with("(999: 999) -> TAIL"));
+    assert!(!bottom.contains("(8: 8)"));
+    assert!(!bottom.contains("(991: 991)"));
+
+    // Lists up to the threshold are still drawn in full
+    let short = SkipList::from_sorted((0..64).map(|i| (i, i)));
+    assert!(!short.format_tower().contains("..."));
+    assert!(short.format_tower().contains("(32: 32)"));
+}
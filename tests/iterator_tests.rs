@@ -1,4 +1,4 @@
-use skiplist::SkipList;
+use skiplist::{MergeSide, SkipList};
 
 #[test]
 fn test_borrowed_iterator() {
@@ -148,4 +148,386 @@ fn test_iterator_with_complex_types() {
     assert_eq!(collected[0].0.name, "Alice");
     assert_eq!(collected[1].0.name, "Bob");
     assert_eq!(collected[2].0.name, "Charlie");
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_enumerate_keys_matches_iter_enumerate() {
+    let mut skip_list = SkipList::new();
+
+    for i in [42, 17, 8, 23, 4, 15, 31] {
+        skip_list.insert(i, i * 2);
+    }
+
+    let enumerated: Vec<_> = skip_list.enumerate_keys().collect();
+    let expected: Vec<_> = skip_list.iter().enumerate().map(|(i, (k, _))| (i, k)).collect();
+    assert_eq!(enumerated, expected);
+
+    // Positions agree with index()
+    for (pos, key) in skip_list.enumerate_keys() {
+        assert_eq!(skip_list.index(pos).map(|(k, _)| k), Some(key));
+    }
+
+    let empty: SkipList<i32, i32> = SkipList::new();
+    assert_eq!(empty.enumerate_keys().count(), 0);
+}
+
+#[test]
+fn test_into_sorted_vec_round_trip() {
+    let mut entries: Vec<_> = [42, 17, 8, 23, 4, 15, 31].iter().map(|&k| (k, k.to_string())).collect();
+
+    let mut skip_list = SkipList::new();
+    for (k, v) in entries.clone() {
+        skip_list.insert(k, v);
+    }
+
+    let vec = skip_list.into_sorted_vec();
+    entries.sort();
+    assert_eq!(vec, entries);
+    assert_eq!(vec.capacity(), entries.len());
+
+    let empty: SkipList<i32, i32> = SkipList::new();
+    assert_eq!(empty.into_sorted_vec(), vec![]);
+}
+
+#[test]
+fn test_find_and_try_fold_stop_early() {
+    let skip_list = SkipList::from_sorted((0..1_000_000).map(|i| (i, i)));
+
+    let mut visited = 0;
+    let mut iter = skip_list.iter();
+    let found = iter.find(|&(&k, _)| {
+        visited += 1;
+        k == 10
+    });
+    assert_eq!(found, Some((&10, &10)));
+    assert_eq!(visited, 11);
+
+    // The iterator resumes right after the match
+    assert_eq!(iter.next(), Some((&11, &11)));
+
+    let mut visited = 0;
+    let mut iter = skip_list.iter();
+    let result: Result<i64, i32> = iter.try_fold(0i64, |acc, (&k, _)| {
+        visited += 1;
+        if k == 99 { Err(k) } else { Ok(acc + k as i64) }
+    });
+    assert_eq!(result, Err(99));
+    assert_eq!(visited, 100);
+    assert_eq!(iter.next(), Some((&100, &100)));
+
+    // A full fold still visits everything
+    let sum = skip_list.iter().fold(0i64, |acc, (&k, _)| acc + k as i64);
+    assert_eq!(sum, (0..1_000_000i64).sum());
+}
+
+#[test]
+fn test_top_k_by_value() {
+    let mut skip_list = SkipList::new();
+    let weights = [("a", 5), ("b", 12), ("c", 3), ("d", 12), ("e", 8), ("f", 1)];
+    for (key, weight) in weights {
+        skip_list.insert(key, weight);
+    }
+
+    let top: Vec<_> = skip_list
+        .top_k_by_value(3)
+        .into_iter()
+        .map(|(&k, &v)| (k, v))
+        .collect();
+    // Ties keep key order
+    assert_eq!(top, vec![("b", 12), ("d", 12), ("e", 8)]);
+
+    assert_eq!(skip_list.indices_sorted_by_value(), vec![1, 3, 4, 0, 2, 5]);
+    assert_eq!(skip_list.top_k_by_value(100).len(), 6);
+    assert!(skip_list.top_k_by_value(0).is_empty());
+    assert!(SkipList::<i32, i32>::new().indices_sorted_by_value().is_empty());
+}
+
+#[test]
+fn test_intersection_and_difference() {
+    let mut a = SkipList::new();
+    let mut b = SkipList::new();
+    for i in 0..100 {
+        if i % 2 == 0 {
+            a.insert(i, i);
+        }
+        if i % 3 == 0 {
+            b.insert(i, -i);
+        }
+    }
+
+    let both: Vec<_> = a.intersection(&b).map(|(&k, &v)| (k, v)).collect();
+    let expected: Vec<_> = (0..100).filter(|i| i % 6 == 0).map(|i| (i, i)).collect();
+    assert_eq!(both, expected);
+
+    let only_a: Vec<_> = a.difference(&b).map(|(&k, _)| k).collect();
+    let expected: Vec<_> = (0..100).filter(|i| i % 2 == 0 && i % 3 != 0).collect();
+    assert_eq!(only_a, expected);
+
+    // Values always come from `self`
+    assert!(b.intersection(&a).all(|(&k, &v)| v == -k));
+    assert_eq!(b.difference(&a).count(), b.len() - b.intersection(&a).count());
+
+    let empty = SkipList::new();
+    assert_eq!(a.intersection(&empty).count(), 0);
+    assert_eq!(a.difference(&empty).count(), a.len());
+    assert_eq!(empty.difference(&a).count(), 0);
+    assert_eq!(a.intersection(&a).count(), a.len());
+    assert_eq!(a.difference(&a).count(), 0);
+}
+
+#[test]
+fn test_symmetric_difference() {
+    let mut a = SkipList::new();
+    let mut b = SkipList::new();
+    for k in [1, 4, 5, 9] {
+        a.insert(k, "a");
+    }
+    for k in [0, 5, 7] {
+        b.insert(k, "b");
+    }
+
+    // The shared key 5 is excluded, every other key appears once
+    let entries: Vec<_> = a.symmetric_difference(&b).map(|(&k, &v)| (k, v)).collect();
+    assert_eq!(
+        entries,
+        vec![(0, "b"), (1, "a"), (4, "a"), (7, "b"), (9, "a")]
+    );
+
+    let reversed: Vec<_> = b.symmetric_difference(&a).map(|(&k, _)| k).collect();
+    assert_eq!(reversed, vec![0, 1, 4, 7, 9]);
+
+    let empty = SkipList::new();
+    assert_eq!(a.symmetric_difference(&empty).count(), 4);
+    assert_eq!(empty.symmetric_difference(&b).count(), 3);
+    assert_eq!(a.symmetric_difference(&a).count(), 0);
+}
+
+#[test]
+fn test_keys_subset_and_superset() {
+    let build = |keys: &[i32]| {
+        let mut skip_list = SkipList::new();
+        for &k in keys {
+            skip_list.insert(k, k);
+        }
+        skip_list
+    };
+
+    let small = build(&[1, 2]);
+    let large = build(&[1, 2, 3]);
+    let other = build(&[2, 3, 4]);
+    let empty = build(&[]);
+
+    assert!(small.keys_subset_of(&large));
+    assert!(large.keys_superset_of(&small));
+    assert!(!large.keys_subset_of(&small));
+    assert!(!small.keys_superset_of(&large));
+
+    // Not a subset despite being smaller
+    assert!(!small.keys_subset_of(&other));
+    assert!(!other.keys_superset_of(&small));
+
+    assert!(empty.keys_subset_of(&small));
+    assert!(small.keys_superset_of(&empty));
+    assert!(large.keys_subset_of(&large));
+    assert!(large.keys_superset_of(&large));
+}
+
+#[test]
+fn test_iter_nth_matches_linear() {
+    let mut skip_list = SkipList::new();
+    for i in 0..500 {
+        skip_list.insert(i, i * 2);
+    }
+
+    for start in [0, 1, 17, 250, 498, 499, 500] {
+        for n in [0, 1, 2, 5, 31, 100, 249, 250, 499, 500, 1000] {
+            let mut fast = skip_list.iter();
+            let mut linear = skip_list.iter();
+            for _ in 0..start {
+                fast.next();
+                linear.next();
+            }
+
+            // Linear reference: `next` n times, then one more
+            let expected = (0..n).try_for_each(|_| linear.next().map(|_| ())).and_then(|_| linear.next());
+            assert_eq!(fast.nth(n), expected, "start {} n {}", start, n);
+            // Both iterators continue from the same place afterwards
+            assert_eq!(fast.next(), linear.next(), "start {} n {}", start, n);
+        }
+    }
+
+    let stepped: Vec<_> = skip_list.iter().step_by(37).map(|(&k, _)| k).collect();
+    assert_eq!(stepped, (0..500).step_by(37).collect::<Vec<_>>());
+
+    assert_eq!(SkipList::<i32, i32>::new().iter().nth(3), None);
+}
+
+#[test]
+fn test_consuming_iterator_peek() {
+    let mut skip_list = SkipList::new();
+    for i in 0..10 {
+        skip_list.insert(i, format!("v{}", i));
+    }
+
+    let mut iter = skip_list.into_iter();
+    assert_eq!(iter.peek(), Some((&0, &"v0".to_string())));
+    assert_eq!(iter.peek(), Some((&0, &"v0".to_string())));
+    assert_eq!(iter.next(), Some((0, "v0".to_string())));
+
+    // Peeking further ahead after some entries were consumed
+    iter.next();
+    iter.next();
+    assert_eq!(iter.peek_nth(0), Some((&3, &"v3".to_string())));
+    assert_eq!(iter.peek_nth(6), Some((&9, &"v9".to_string())));
+    assert_eq!(iter.peek_nth(7), None);
+    assert_eq!(iter.len(), 7);
+
+    // Every peek agrees with the following next
+    while let Some((&k, v)) = iter.peek() {
+        let v = v.clone();
+        assert_eq!(iter.next(), Some((k, v)));
+    }
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.peek(), None);
+}
+
+#[test]
+fn test_filter_map_into() {
+    let mut skip_list = SkipList::new();
+    for i in 0..100 {
+        skip_list.insert(i, i * 10);
+    }
+
+    // Keep even keys only, changing the value type
+    let evens = skip_list.filter_map_into(|k, v| (k % 2 == 0).then(|| (k, v.to_string())));
+    assert_eq!(evens.len(), 50);
+    assert!(evens.iter().map(|(&k, _)| k).eq((0..100).step_by(2)));
+    assert_eq!(evens.get(&42), Some(&"420".to_string()));
+    assert_eq!(evens.get(&41), None);
+    assert_eq!(evens.index(49), Some((&98, &"980".to_string())));
+
+    // Reordered and colliding keys: sorted, and the later entry wins
+    let folded = evens.filter_map_into(|k, v| Some((-(k % 10), v)));
+    let entries: Vec<_> = folded.iter().map(|(&k, v)| (k, v.clone())).collect();
+    assert_eq!(
+        entries,
+        vec![
+            (-8, "980".to_string()),
+            (-6, "960".to_string()),
+            (-4, "940".to_string()),
+            (-2, "920".to_string()),
+            (0, "900".to_string()),
+        ]
+    );
+
+    let none = folded.filter_map_into(|_, _| None::<(i32, ())>);
+    assert!(none.is_empty());
+}
+
+#[test]
+fn test_into_iter_rev() {
+    let skip_list = SkipList::from_sorted([(1, 'a'), (2, 'b'), (3, 'c')]);
+    let entries: Vec<_> = skip_list.into_iter_rev().collect();
+    assert_eq!(entries, vec![(3, 'c'), (2, 'b'), (1, 'a')]);
+
+    let mut skip_list = SkipList::new();
+    for i in 0..500 {
+        skip_list.insert(i, i.to_string());
+    }
+    let mut iter = skip_list.into_iter_rev();
+    assert_eq!(iter.len(), 500);
+    assert_eq!(iter.next(), Some((499, "499".to_string())));
+    assert_eq!(iter.len(), 499);
+    assert!(iter.map(|(k, _)| k).eq((0..499).rev()));
+
+    assert_eq!(SkipList::<i32, i32>::new().into_iter_rev().next(), None);
+}
+
+#[test]
+fn test_into_iter_rev_early_drop_frees_the_rest() {
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    struct Tracked(Rc<Cell<usize>>);
+    impl Drop for Tracked {
+        fn drop(&mut self) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    let drops = Rc::new(Cell::new(0));
+    let mut skip_list = SkipList::new();
+    for i in 0..100 {
+        skip_list.insert(i, Tracked(Rc::clone(&drops)));
+    }
+
+    let mut iter = skip_list.into_iter_rev();
+    for expected in (90..100).rev() {
+        let (k, _) = iter.next().unwrap();
+        assert_eq!(k, expected);
+    }
+    assert_eq!(drops.get(), 10);
+
+    // The 90 entries never yielded are dropped with the iterator, once each
+    drop(iter);
+    assert_eq!(drops.get(), 100);
+}
+
+#[test]
+fn test_chunks() {
+    let skip_list = SkipList::from_sorted((0..10).map(|i| (i, i * 10)));
+
+    let sizes: Vec<_> = skip_list.chunks(3).map(|chunk| chunk.len()).collect();
+    assert_eq!(sizes, vec![3, 3, 3, 1]);
+    let flattened: Vec<_> = skip_list.chunks(3).flatten().collect();
+    assert_eq!(flattened, skip_list.iter().collect::<Vec<_>>());
+    assert_eq!(skip_list.chunks(3).last(), Some(vec![(&9, &90)]));
+
+    // Exact multiples leave no partial group, oversized groups take everything
+    assert_eq!(skip_list.chunks(5).count(), 2);
+    assert_eq!(skip_list.chunks(100).next().map(|chunk| chunk.len()), Some(10));
+    assert_eq!(SkipList::<i32, i32>::new().chunks(3).next(), None);
+}
+
+#[test]
+#[should_panic(expected = "chunk size must be non-zero")]
+fn test_chunks_zero_size() {
+    let skip_list = SkipList::from_sorted([(1, 1)]);
+    let _ = skip_list.chunks(0);
+}
+
+#[test]
+fn test_merge_join() {
+    let a = SkipList::from_sorted([(1, 'a'), (3, 'b'), (5, 'c'), (6, 'd')]);
+    let b = SkipList::from_sorted([(0, 'w'), (3, 'x'), (6, 'y'), (8, 'z')]);
+
+    let sides: Vec<_> = a.merge_join(&b).collect();
+    assert_eq!(
+        sides,
+        vec![
+            MergeSide::Right((&0, &'w')),
+            MergeSide::Left((&1, &'a')),
+            MergeSide::Both((&3, &'b'), (&3, &'x')),
+            MergeSide::Left((&5, &'c')),
+            MergeSide::Both((&6, &'d'), (&6, &'y')),
+            MergeSide::Right((&8, &'z')),
+        ]
+    );
+
+    // Swapping the lists swaps the sides
+    let swapped: Vec<_> = b.merge_join(&a).collect();
+    let mirrored: Vec<_> = sides
+        .into_iter()
+        .map(|side| match side {
+            MergeSide::Both(l, r) => MergeSide::Both(r, l),
+            MergeSide::Left(l) => MergeSide::Right(l),
+            MergeSide::Right(r) => MergeSide::Left(r),
+        })
+        .collect();
+    assert_eq!(swapped, mirrored);
+
+    let empty = SkipList::new();
+    assert!(a.merge_join(&empty).all(|side| matches!(side, MergeSide::Left(_))));
+    assert!(empty.merge_join(&b).all(|side| matches!(side, MergeSide::Right(_))));
+    assert_eq!(a.merge_join(&a).filter(|side| matches!(side, MergeSide::Both(..))).count(), 4);
+}
@@ -0,0 +1,59 @@
+#![cfg(feature = "rayon")]
+use rayon::prelude::*;
+use skiplist::{NodePool, SkipList};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Counts how many times it has been dropped, from any thread.
+struct Tracked(Arc<AtomicUsize>);
+
+impl Drop for Tracked {
+    fn drop(&mut self) {
+        self.0.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+#[test]
+fn test_into_par_iter_matches_sequential() {
+    let skip_list = SkipList::from_sorted((0..100_000u64).map(|i| (i, i * 3)));
+    let sequential: u64 = skip_list.iter().map(|(_, &v)| v).sum();
+
+    let parallel: u64 = skip_list.clone().into_par_iter().map(|(_, v)| v).sum();
+    assert_eq!(parallel, sequential);
+
+    // Order-preserving adaptors see the entries in key order
+    let keys: Vec<u64> = skip_list.into_par_iter().map(|(k, _)| k).collect();
+    assert!(keys.iter().copied().eq(0..100_000));
+
+    let empty = SkipList::<u64, u64>::new();
+    assert_eq!(empty.into_par_iter().count(), 0);
+}
+
+#[test]
+fn test_into_par_iter_drops_every_entry_once() {
+    let drops = Arc::new(AtomicUsize::new(0));
+    let skip_list = SkipList::from_sorted((0..10_000).map(|i| (i, Tracked(Arc::clone(&drops)))));
+
+    // Half of the entries are dropped by the filter, the rest after collecting
+    let kept: Vec<_> = skip_list
+        .into_par_iter()
+        .filter(|(k, _)| k % 2 == 0)
+        .collect();
+    assert_eq!(drops.load(Ordering::Relaxed), 5_000);
+
+    drop(kept);
+    assert_eq!(drops.load(Ordering::Relaxed), 10_000);
+}
+
+#[test]
+fn test_into_par_iter_returns_nodes_to_the_pool() {
+    let pool = NodePool::new();
+    let mut skip_list = SkipList::with_pool(&pool);
+    for i in 0..1000 {
+        skip_list.insert(i, i);
+    }
+
+    let total: i64 = skip_list.into_par_iter().map(|(_, v)| v as i64).sum();
+    assert_eq!(total, 499_500);
+    assert_eq!(pool.len(), 1000);
+}
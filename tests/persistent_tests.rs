@@ -0,0 +1,99 @@
+use skiplist::PersistentSkipList;
+use std::collections::BTreeMap;
+
+fn entries(list: &PersistentSkipList<i32, i32>) -> Vec<(i32, i32)> {
+    list.iter().map(|(&k, &v)| (k, v)).collect()
+}
+
+#[test]
+fn test_snapshot_does_not_see_later_insert() {
+    let mut list = PersistentSkipList::new();
+    for i in 0..100 {
+        list = list.insert(i * 2, i);
+    }
+
+    let snapshot = list.clone();
+    let list = list.insert(51, -1);
+
+    assert_eq!(list.get(&51), Some(&-1));
+    assert_eq!(snapshot.get(&51), None);
+    assert_eq!(list.len(), 101);
+    assert_eq!(snapshot.len(), 100);
+}
+
+#[test]
+fn test_versions_stay_valid() {
+    let empty = PersistentSkipList::new();
+    let one = empty.insert(1, 10);
+    let two = one.insert(2, 20);
+    let replaced = two.insert(1, 11);
+    let removed = replaced.remove(&2);
+    let missing = removed.remove(&7);
+
+    assert_eq!(entries(&empty), vec![]);
+    assert_eq!(entries(&one), vec![(1, 10)]);
+    assert_eq!(entries(&two), vec![(1, 10), (2, 20)]);
+    assert_eq!(entries(&replaced), vec![(1, 11), (2, 20)]);
+    assert_eq!(entries(&removed), vec![(1, 11)]);
+    assert_eq!(entries(&missing), vec![(1, 11)]);
+
+    assert_eq!(replaced.len(), 2);
+    assert_eq!(removed.len(), 1);
+    assert!(!removed.contains_key(&2));
+    assert!(two.contains_key(&2));
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn test_every_version_matches_model() {
+    let mut versions = vec![(PersistentSkipList::new(), BTreeMap::new())];
+
+    // Deterministic mix of inserts, replacements and removals, each applied
+    // to the latest version
+    let mut x: u32 = 12345;
+    for step in 0..2000 {
+        x = x.wrapping_mul(1103515245).wrapping_add(12345);
+        let key = ((x >> 16) % 300) as i32;
+
+        let (list, model) = versions.last().unwrap();
+        let mut model = model.clone();
+        let list = if x.is_multiple_of(3) {
+            model.remove(&key);
+            list.remove(&key)
+        } else {
+            model.insert(key, step);
+            list.insert(key, step)
+        };
+        versions.push((list, model));
+    }
+
+    // Older versions are unaffected by everything applied after them
+    for (list, model) in versions.iter().step_by(50) {
+        let expected: Vec<_> = model.iter().map(|(&k, &v)| (k, v)).collect();
+        assert_eq!(entries(list), expected);
+        assert_eq!(list.len(), model.len());
+        assert_eq!(list.iter().len(), model.len());
+        for key in -1..301 {
+            assert_eq!(list.get(&key), model.get(&key), "key {}", key);
+        }
+    }
+}
+
+#[test]
+fn test_remove_everything() {
+    let mut list = PersistentSkipList::new();
+    for i in 0..500 {
+        list = list.insert(i, i);
+    }
+    let full = list.clone();
+
+    for i in (0..500).rev().step_by(2).chain((0..500).step_by(2)) {
+        list = list.remove(&i);
+        assert!(!list.contains_key(&i));
+    }
+
+    assert!(list.is_empty());
+    assert_eq!(list.iter().next(), None);
+    assert_eq!(full.len(), 500);
+    assert_eq!(format!("{:?}", list.insert(1, 2)), "{1: 2}");
+}
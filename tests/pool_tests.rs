@@ -0,0 +1,164 @@
+use skiplist::{NodePool, SkipList};
+use std::cell::Cell;
+use std::rc::Rc;
+
+/// Counts how many times it has been dropped.
+#[derive(Debug)]
+struct Tracked(Rc<Cell<usize>>);
+
+impl Drop for Tracked {
+    fn drop(&mut self) {
+        self.0.set(self.0.get() + 1);
+    }
+}
+
+#[test]
+fn test_pool_recycles_nodes() {
+    let pool = NodePool::new();
+    assert!(pool.is_empty());
+
+    let mut skip_list = SkipList::with_pool(&pool);
+    for i in 0..100 {
+        skip_list.insert(i, i);
+    }
+    assert!(pool.is_empty());
+
+    // Every removal path hands its node back
+    skip_list.remove(&50);
+    assert_eq!(pool.len(), 1);
+    skip_list.remove_first_n(4);
+    skip_list.remove_last_n(5);
+    assert_eq!(pool.len(), 10);
+    skip_list.retain(|&k, _| k % 2 == 0);
+    let remaining = skip_list.len();
+    assert_eq!(pool.len(), 100 - remaining);
+
+    // Inserts draw from the pool first
+    for i in 100..110 {
+        skip_list.insert(i, i);
+    }
+    assert_eq!(pool.len(), 90 - remaining);
+    assert_eq!(skip_list.get(&105), Some(&105));
+
+    let drained: Vec<_> = skip_list.into_iter().collect();
+    assert_eq!(drained.len(), remaining + 10);
+    assert_eq!(pool.len(), 100);
+
+    // A second list reuses the same allocations
+    let mut skip_list = SkipList::with_pool(&pool);
+    for i in (0..150).rev() {
+        skip_list.insert(i, i * 2);
+    }
+    assert!(pool.is_empty());
+    for i in 0..150 {
+        assert_eq!(skip_list.index(i as usize), Some((&i, &(i * 2))));
+    }
+    drop(skip_list);
+    assert_eq!(pool.len(), 150);
+
+    pool.clear();
+    assert!(pool.is_empty());
+}
+
+#[test]
+fn test_pool_still_drops_values() {
+    let drops = Rc::new(Cell::new(0));
+    let pool = NodePool::new();
+
+    let mut skip_list = SkipList::with_pool(&pool);
+    for i in 0..20 {
+        skip_list.insert(i, Tracked(drops.clone()));
+    }
+
+    drop(skip_list.remove(&3));
+    assert_eq!(drops.get(), 1);
+    skip_list.remove_first_n(2);
+    skip_list.remove_last_n(2);
+    assert_eq!(drops.get(), 5);
+
+    // Replacing a value drops the old one
+    skip_list.insert(10, Tracked(drops.clone()));
+    assert_eq!(drops.get(), 6);
+
+    drop(skip_list);
+    assert_eq!(drops.get(), 6 + 15);
+    assert_eq!(pool.len(), 20);
+}
+
+#[test]
+fn test_dropping_list_drops_values() {
+    let drops = Rc::new(Cell::new(0));
+
+    let mut skip_list = SkipList::new();
+    for i in 0..10 {
+        skip_list.insert(i, Tracked(drops.clone()));
+    }
+    drop(skip_list);
+    assert_eq!(drops.get(), 10);
+
+    let mut skip_list = SkipList::new();
+    for i in 0..10 {
+        skip_list.insert(i, Tracked(drops.clone()));
+    }
+    let mut iter = skip_list.into_iter();
+    iter.next();
+    drop(iter);
+    assert_eq!(drops.get(), 20);
+}
+
+#[test]
+fn test_clear() {
+    let drops = Rc::new(Cell::new(0));
+    let mut skip_list = SkipList::new();
+    for i in 0..100 {
+        skip_list.insert(i, Tracked(drops.clone()));
+    }
+
+    skip_list.clear();
+    assert_eq!(drops.get(), 100);
+    assert!(skip_list.is_empty());
+    assert_eq!(skip_list.iter().count(), 0);
+    skip_list.assert_invariants();
+
+    // Still usable, and clearing an empty list is a no-op
+    skip_list.insert(5, Tracked(drops.clone()));
+    assert_eq!(skip_list.len(), 1);
+    skip_list.clear();
+    skip_list.clear();
+    assert_eq!(drops.get(), 101);
+}
+
+#[test]
+fn test_clear_retaining_capacity() {
+    let drops = Rc::new(Cell::new(0));
+    let mut skip_list = SkipList::new();
+
+    for round in 0..3 {
+        for i in (0..200).rev() {
+            skip_list.insert(i, Tracked(drops.clone()));
+        }
+        skip_list.assert_invariants();
+        #[cfg(feature = "test-utils")]
+        assert!(skip_list.verify_spans());
+        assert_eq!(skip_list.len(), 200);
+
+        // Values are dropped at once, not when the nodes are reused
+        skip_list.clear_retaining_capacity();
+        assert_eq!(drops.get(), 200 * (round + 1));
+        assert!(skip_list.is_empty());
+        skip_list.assert_invariants();
+    }
+
+    // An existing pool receives the allocations
+    let pool = NodePool::new();
+    let mut skip_list = SkipList::with_pool(&pool);
+    for i in 0..50 {
+        skip_list.insert(i, i);
+    }
+    skip_list.clear_retaining_capacity();
+    assert_eq!(pool.len(), 50);
+    for i in 0..30 {
+        skip_list.insert(i, i);
+    }
+    assert_eq!(pool.len(), 20);
+}
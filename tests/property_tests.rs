@@ -400,4 +400,41 @@ fn test_large_dataset_property() {
     // Final comprehensive span verification
     #[cfg(feature = "test-utils")]
     assert!(skip_list.verify_spans(), "Final span verification failed for large dataset");
-}
\ No newline at end of file
+}
+
+#[cfg(feature = "test-utils")]
+proptest! {
+    #[test]
+    fn test_lookups_invariant_under_tower_reshaping(
+        batches in prop::collection::vec(prop::collection::vec(0i32..500, 0..40), 1..6),
+        seed in any::<u64>()
+    ) {
+        use rand::{SeedableRng, rngs::StdRng};
+
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut skip_list = SkipList::new();
+        let mut btree = BTreeMap::new();
+
+        for batch in batches {
+            for key in batch {
+                if key % 5 == 0 {
+                    prop_assert_eq!(skip_list.remove(&key), btree.remove(&key));
+                } else {
+                    prop_assert_eq!(skip_list.insert(key, key * 2), btree.insert(key, key * 2));
+                }
+            }
+
+            skip_list.rebuild_random_levels(&mut rng);
+            prop_assert!(skip_list.verify_spans(), "Span verification failed after reshaping");
+            prop_assert_eq!(skip_list.len(), btree.len());
+
+            for (idx, (key, value)) in btree.iter().enumerate() {
+                prop_assert_eq!(skip_list.get(key), Some(value));
+                prop_assert_eq!(skip_list.index(idx), Some((key, value)));
+                prop_assert_eq!(skip_list.count_lt(key), idx);
+            }
+            prop_assert_eq!(skip_list.get(&-1), None);
+            prop_assert_eq!(skip_list.index(btree.len()), None);
+        }
+    }
+}
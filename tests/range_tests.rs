@@ -0,0 +1,526 @@
+use skiplist::SkipList;
+use std::collections::BTreeMap;
+use std::ops::{Bound, RangeBounds};
+
+fn build(n: i32) -> SkipList<i32, i32> {
+    let mut skip_list = SkipList::new();
+    for i in 0..n {
+        skip_list.insert(i, i * 10);
+    }
+    skip_list
+}
+
+#[test]
+fn test_range_matches_btreemap() {
+    let skip_list = build(100);
+    let btree: BTreeMap<i32, i32> = (0..100).map(|i| (i, i * 10)).collect();
+
+    let bounds = [
+        (Bound::Included(10), Bound::Excluded(50)),
+        (Bound::Included(10), Bound::Included(50)),
+        (Bound::Excluded(10), Bound::Excluded(50)),
+        (Bound::Excluded(10), Bound::Included(50)),
+        (Bound::Unbounded, Bound::Excluded(5)),
+        (Bound::Included(95), Bound::Unbounded),
+        (Bound::Unbounded, Bound::Unbounded),
+        (Bound::Included(-10), Bound::Included(200)),
+        (Bound::Included(150), Bound::Unbounded),
+    ];
+
+    for range in bounds {
+        let actual: Vec<_> = skip_list.range(range).collect();
+        let expected: Vec<_> = btree.range(range).collect();
+        assert_eq!(actual, expected, "range {:?}", range);
+        assert_eq!(skip_list.range(range).len(), expected.len());
+    }
+}
+
+#[test]
+fn test_range_empty_and_reversed() {
+    let skip_list = build(10);
+
+    assert_eq!(skip_list.range(5..5).count(), 0);
+    assert_eq!(skip_list.range((Bound::Excluded(5), Bound::Excluded(6))).count(), 0);
+    assert_eq!(skip_list.range((Bound::Included(7), Bound::Excluded(3))).count(), 0);
+
+    let empty: SkipList<i32, i32> = SkipList::new();
+    assert_eq!(empty.range(..).count(), 0);
+}
+
+#[test]
+fn test_is_empty_range() {
+    let skip_list = SkipList::from_sorted([10, 20, 30, 40, 50].map(|k| (k, ())));
+    let empty: SkipList<i32, ()> = SkipList::new();
+
+    // a..a is always empty, a..=a holds exactly a
+    for (a, present) in [(20, true), (25, false), (10, true), (50, true)] {
+        assert!(skip_list.is_empty_range(a..a));
+        assert_eq!(!skip_list.is_empty_range(a..=a), present);
+        assert!(skip_list.is_empty_range((Bound::Excluded(a), Bound::Included(a))));
+        assert!(skip_list.is_empty_range((Bound::Excluded(a), Bound::Excluded(a))));
+    }
+    assert!(skip_list.is_empty_range((Bound::Included(5), Bound::Included(4))));
+    assert!(skip_list.is_empty_range((Bound::Excluded(20), Bound::Excluded(30))));
+    assert!(!skip_list.is_empty_range((Bound::Excluded(20), Bound::Included(30))));
+
+    // Fully below, fully above and reversed
+    assert!(skip_list.is_empty_range(..10));
+    assert!(skip_list.is_empty_range(0..=9));
+    assert!(skip_list.is_empty_range((Bound::Excluded(50), Bound::Unbounded)));
+    assert!(skip_list.is_empty_range(51..100));
+    assert!(skip_list.is_empty_range((Bound::Included(40), Bound::Excluded(20))));
+    assert!(!skip_list.is_empty_range(..));
+
+    assert!(empty.is_empty_range(..));
+    assert!(empty.is_empty_range(0..=100));
+    assert!(empty.is_empty_range((Bound::Excluded(5), Bound::Excluded(5))));
+
+    // Every combination of bounds agrees with filtering the keys, and with
+    // the other methods built on the same bound resolution
+    let bound = |kind: u8, key: i32| match kind {
+        0 => Bound::Included(key),
+        1 => Bound::Excluded(key),
+        _ => Bound::Unbounded,
+    };
+    for start in (0..=60).step_by(5) {
+        for end in (0..=60).step_by(5) {
+            for (start_kind, end_kind) in (0..3).flat_map(|s| (0..3).map(move |e| (s, e))) {
+                let range = (bound(start_kind, start), bound(end_kind, end));
+                let count = skip_list.iter().filter(|(k, _)| range.contains(k)).count();
+
+                assert_eq!(skip_list.is_empty_range(range), count == 0, "{:?}", range);
+                assert_eq!(skip_list.range(range).count(), count, "{:?}", range);
+                assert_eq!(skip_list.range_span(range).map_or(0, |(n, _, _)| n), count);
+                assert!(empty.is_empty_range(range));
+            }
+        }
+    }
+}
+
+#[test]
+fn test_keys_and_values_in_range() {
+    let skip_list = build(100);
+
+    let keys: Vec<_> = skip_list.keys_in_range(10..50).copied().collect();
+    assert_eq!(keys, (10..50).collect::<Vec<_>>());
+
+    let values: Vec<_> = skip_list.values_in_range(10..50).copied().collect();
+    assert_eq!(values, (10..50).map(|i| i * 10).collect::<Vec<_>>());
+
+    assert_eq!(skip_list.keys_in_range(200..).count(), 0);
+}
+
+#[test]
+fn test_range_with_borrowed_keys() {
+    let mut skip_list = SkipList::new();
+    for word in ["apple", "banana", "cherry", "date"] {
+        skip_list.insert(word.to_string(), word.len());
+    }
+
+    let keys: Vec<_> = skip_list
+        .keys_in_range::<str, _>((Bound::Included("b"), Bound::Excluded("d")))
+        .collect();
+    assert_eq!(keys, vec!["banana", "cherry"]);
+}
+
+#[test]
+fn test_range_bounds_entries() {
+    let skip_list = build(100);
+
+    assert_eq!(
+        skip_list.range_bounds_entries(10..90),
+        Some(((&10, &100), (&89, &890)))
+    );
+    assert_eq!(
+        skip_list.range_bounds_entries(10..=90),
+        Some(((&10, &100), (&90, &900)))
+    );
+    assert_eq!(
+        skip_list.range_bounds_entries(..),
+        Some(((&0, &0), (&99, &990)))
+    );
+    assert_eq!(
+        skip_list.range_bounds_entries(42..43),
+        Some(((&42, &420), (&42, &420)))
+    );
+    assert_eq!(skip_list.range_bounds_entries(42..42), None);
+    assert_eq!(skip_list.range_bounds_entries(200..), None);
+
+    let empty: SkipList<i32, i32> = SkipList::new();
+    assert_eq!(empty.range_bounds_entries(..), None);
+}
+
+#[test]
+fn test_range_span() {
+    let skip_list = build(100);
+
+    assert_eq!(
+        skip_list.range_span(10..90),
+        Some((80, (&10, &100), (&89, &890)))
+    );
+    assert_eq!(
+        skip_list.range_span((Bound::Excluded(10), Bound::Included(90))),
+        Some((80, (&11, &110), (&90, &900)))
+    );
+    assert_eq!(skip_list.range_span(..), Some((100, (&0, &0), (&99, &990))));
+    assert_eq!(skip_list.range_span(99..), Some((1, (&99, &990), (&99, &990))));
+    assert_eq!(skip_list.range_span((Bound::Included(50), Bound::Excluded(20))), None);
+    assert_eq!(skip_list.range_span(-5..0), None);
+
+    // Counts agree with iterating the range
+    for (start, end) in [(0, 1), (3, 97), (-10, 40), (60, 1000)] {
+        let count = skip_list.range(start..end).count();
+        assert_eq!(skip_list.range_span(start..end).map(|(n, _, _)| n), Some(count));
+    }
+}
+
+#[test]
+fn test_position_range_inclusive() {
+    let skip_list = build(10);
+    let len = skip_list.len();
+    let keys = |range: std::ops::RangeInclusive<usize>| -> Vec<i32> {
+        skip_list.position_range(range).map(|(&k, _)| k).collect()
+    };
+
+    assert_eq!(keys(0..=0), vec![0]);
+    assert_eq!(keys(3..=5), vec![3, 4, 5]);
+    assert_eq!(keys(0..=len - 1), (0..10).collect::<Vec<_>>());
+    assert_eq!(keys(7..=len - 1), vec![7, 8, 9]);
+    assert_eq!(keys(len - 1..=len - 1), vec![9]);
+
+    // Past-the-end upper bound clamps to the last position
+    assert_eq!(keys(0..=len), keys(0..=len - 1));
+    assert_eq!(keys(8..=usize::MAX), vec![8, 9]);
+    assert_eq!(keys(len..=len), Vec::<i32>::new());
+}
+
+#[test]
+fn test_position_range_bounds() {
+    let skip_list = build(10);
+    let keys = |range: (Bound<usize>, Bound<usize>)| -> Vec<i32> {
+        skip_list.position_range(range).map(|(&k, _)| k).collect()
+    };
+
+    assert_eq!(skip_list.position_range(..=2).len(), 3);
+    assert_eq!(skip_list.position_range(..3).len(), 3);
+    assert_eq!(skip_list.position_range(7..).len(), 3);
+    assert_eq!(skip_list.position_range(..).len(), 10);
+    assert_eq!(keys((Bound::Excluded(2), Bound::Included(4))), vec![3, 4]);
+    assert_eq!(keys((Bound::Excluded(2), Bound::Excluded(4))), vec![3]);
+
+    // Empty and reversed ranges
+    assert_eq!(skip_list.position_range(4..4).count(), 0);
+    #[allow(clippy::reversed_empty_ranges)]
+    let reversed = skip_list.position_range(5..=2);
+    assert_eq!(reversed.count(), 0);
+    assert_eq!(SkipList::<i32, i32>::new().position_range(..=0).count(), 0);
+
+    let values: Vec<_> = skip_list.position_range(1..=2).map(|(_, &v)| v).collect();
+    assert_eq!(values, vec![10, 20]);
+}
+
+#[test]
+fn test_position_range_mut() {
+    let mut skip_list = build(10);
+
+    for (&k, v) in skip_list.position_range_mut(3..6) {
+        *v = -k;
+    }
+    for i in 0..10 {
+        let expected = if (3..6).contains(&i) { -i } else { i * 10 };
+        assert_eq!(skip_list.index(i as usize), Some((&i, &expected)));
+    }
+
+    // Clamped, empty and reversed ranges
+    assert_eq!(skip_list.position_range_mut(8..100).count(), 2);
+    assert_eq!(skip_list.position_range_mut(..=usize::MAX).count(), 10);
+    assert_eq!(skip_list.position_range_mut(10..).count(), 0);
+    assert_eq!(
+        skip_list
+            .position_range_mut((Bound::Included(6), Bound::Excluded(2)))
+            .count(),
+        0
+    );
+    assert_eq!(SkipList::<i32, i32>::new().position_range_mut(..).count(), 0);
+
+    // All references can be held at once
+    let values: Vec<_> = skip_list.position_range_mut(..2).map(|(_, v)| v).collect();
+    let [a, b] = <[_; 2]>::try_from(values).unwrap();
+    std::mem::swap(a, b);
+    assert_eq!(skip_list.index(0), Some((&0, &10)));
+    assert_eq!(skip_list.index(1), Some((&1, &0)));
+}
+
+#[test]
+fn test_range_sum_via_prefix_sums() {
+    let skip_list = build(50);
+    let prefix = skip_list.build_prefix_sums();
+    assert_eq!(prefix.len(), 50);
+    assert_eq!(prefix[49], (0..50).map(|i| i * 10).sum::<i32>());
+
+    for lo in 0..=50 {
+        for hi in lo..=52 {
+            let expected = skip_list
+                .position_range(lo..hi)
+                .fold(None, |acc: Option<i32>, (_, &v)| Some(acc.unwrap_or(0) + v));
+            assert_eq!(
+                SkipList::<i32, i32>::range_sum_via(&prefix, lo, hi),
+                expected,
+                "range {}..{}",
+                lo,
+                hi
+            );
+        }
+    }
+
+    assert!(SkipList::<i32, i32>::new().build_prefix_sums().is_empty());
+    assert_eq!(SkipList::<i32, i32>::range_sum_via(&[], 0, 3), None);
+}
+
+#[test]
+fn test_range_take() {
+    let skip_list = build(100);
+
+    // Seek into the middle and take 3
+    let mut iter = skip_list.range_take(45..80, 3);
+    assert_eq!(iter.size_hint(), (3, Some(3)));
+    assert_eq!(iter.next(), Some((&45, &450)));
+    assert_eq!(iter.len(), 2);
+    let rest: Vec<_> = iter.map(|(&k, _)| k).collect();
+    assert_eq!(rest, vec![46, 47]);
+
+    // Fewer entries in range than requested
+    let iter = skip_list.range_take(97.., 10);
+    assert_eq!(iter.len(), 3);
+    assert_eq!(iter.map(|(&k, _)| k).collect::<Vec<_>>(), vec![97, 98, 99]);
+
+    assert_eq!(skip_list.range_take(10..20, 0).count(), 0);
+    assert_eq!(skip_list.range_take(200.., 5).count(), 0);
+    assert!(
+        skip_list
+            .range_take(..=30, 7)
+            .eq(skip_list.range(..=30).take(7))
+    );
+}
+
+#[test]
+fn test_reset_range() {
+    let mut skip_list = build(50);
+
+    skip_list.reset_range(10..30, |_| 0);
+    assert_eq!(skip_list.len(), 50);
+    for (i, (&k, &v)) in skip_list.iter().enumerate() {
+        assert_eq!(k, i as i32);
+        let expected = if (10..30).contains(&k) { 0 } else { k * 10 };
+        assert_eq!(v, expected, "key {}", k);
+    }
+
+    // The closure sees each key in order
+    let mut seen = Vec::new();
+    skip_list.reset_range(45.., |&k| {
+        seen.push(k);
+        -k
+    });
+    assert_eq!(seen, vec![45, 46, 47, 48, 49]);
+    assert_eq!(skip_list.get(&47), Some(&-47));
+
+    // Empty ranges leave everything alone
+    skip_list.reset_range(100..200, |_| unreachable!());
+    skip_list.reset_range(5..5, |_| unreachable!());
+    assert_eq!(skip_list.get(&5), Some(&50));
+}
+
+#[test]
+fn test_range_scan_weighted_average() {
+    let skip_list = build(100);
+
+    // Average of the values in [10, 50) weighted by key
+    let (weighted, weights) = skip_list.range_scan(10..50, (0i64, 0i64), |(sum, total), &k, &v| {
+        (sum + k as i64 * v as i64, total + k as i64)
+    });
+    let expected_weighted: i64 = (10..50).map(|k: i64| k * k * 10).sum();
+    let expected_weights: i64 = (10..50).sum();
+    assert_eq!((weighted, weights), (expected_weighted, expected_weights));
+    let average = weighted as f64 / weights as f64;
+    assert!((average - expected_weighted as f64 / expected_weights as f64).abs() < 1e-9);
+
+    // Keys are visited in order
+    let keys = skip_list.range_scan(95.., Vec::new(), |mut keys, &k, _| {
+        keys.push(k);
+        keys
+    });
+    assert_eq!(keys, vec![95, 96, 97, 98, 99]);
+
+    assert_eq!(skip_list.range_scan(200..300, 7, |acc, _, _| acc + 1), 7);
+}
+
+#[test]
+fn test_iter_from_index() {
+    let skip_list = build(50);
+
+    for start in [0, 1, 3, 25, 49, 50, 51, usize::MAX] {
+        assert!(
+            skip_list.iter_from_index(start).eq(skip_list.iter().skip(start)),
+            "start {}",
+            start
+        );
+        assert_eq!(skip_list.iter_from_index(start).len(), 50usize.saturating_sub(start));
+    }
+
+    // Resume paging after a page of 10
+    let page: Vec<_> = skip_list.iter_from_index(10).take(10).map(|(&k, _)| k).collect();
+    assert_eq!(page, (10..20).collect::<Vec<_>>());
+
+    assert_eq!(SkipList::<i32, i32>::new().iter_from_index(0).count(), 0);
+}
+
+#[test]
+fn test_range_values_mut() {
+    let mut skip_list = build(50);
+
+    let values = skip_list.range_values_mut(10..30);
+    assert_eq!(values.len(), 20);
+    for value in values {
+        *value = -*value;
+    }
+    for (&k, &v) in skip_list.iter() {
+        let expected = if (10..30).contains(&k) { -k * 10 } else { k * 10 };
+        assert_eq!(v, expected, "key {}", k);
+    }
+
+    // References come back in key order and can be used together
+    let mut values = skip_list.range_values_mut(45..=47);
+    assert_eq!(values.iter().map(|v| **v).collect::<Vec<_>>(), vec![450, 460, 470]);
+    let (first, rest) = values.split_first_mut().unwrap();
+    std::mem::swap(*first, &mut *rest[1]);
+    assert_eq!(skip_list.get(&45), Some(&470));
+    assert_eq!(skip_list.get(&47), Some(&450));
+
+    assert!(skip_list.range_values_mut(100..200).is_empty());
+    assert!(skip_list.range_values_mut(5..5).is_empty());
+    assert_eq!(skip_list.range_values_mut(..).len(), 50);
+}
+
+#[test]
+fn test_remove_range() {
+    let mut reference: BTreeMap<i32, i32> = (0..300).map(|i| (i * 2, i)).collect();
+    let mut skip_list = SkipList::new();
+    for (&k, &v) in reference.iter().rev() {
+        skip_list.insert(k, v);
+    }
+
+    for (start, end) in [(100, 140), (99, 101), (0, 10), (590, 700), (250, 251), (300, 300)] {
+        let expected = reference.range(start..end).count();
+        reference.retain(|k, _| !(start..end).contains(k));
+
+        assert_eq!(skip_list.remove_range(start..end), expected);
+        skip_list.assert_invariants();
+        assert!(skip_list.iter().eq(reference.iter()));
+    }
+
+    assert_eq!(skip_list.remove_range((Bound::Excluded(200), Bound::Included(180))), 0);
+    assert_eq!(skip_list.remove_range(..), reference.len());
+    assert!(skip_list.is_empty());
+    skip_list.assert_invariants();
+    assert_eq!(skip_list.remove_range(..), 0);
+}
+
+#[test]
+fn test_replace_range() {
+    let mut skip_list = SkipList::new();
+    for i in 0..30 {
+        skip_list.insert(i, "old");
+    }
+
+    skip_list.replace_range(10..20, [(12, "new"), (15, "new")]);
+    skip_list.assert_invariants();
+
+    let expected: Vec<_> = (0..10)
+        .map(|k| (k, "old"))
+        .chain([(12, "new"), (15, "new")])
+        .chain((20..30).map(|k| (k, "old")))
+        .collect();
+    let entries: Vec<_> = skip_list.iter().map(|(&k, &v)| (k, v)).collect();
+    assert_eq!(entries, expected);
+    assert_eq!(skip_list.index(11), Some((&15, &"new")));
+
+    // An empty replacement is a plain removal, an empty range a plain insert
+    skip_list.replace_range(..12, []);
+    assert_eq!(skip_list.index(0), Some((&12, &"new")));
+    skip_list.replace_range(40..40, [(40, "new")]);
+    assert_eq!(skip_list.len(), 13);
+    skip_list.assert_invariants();
+}
+
+#[test]
+fn test_range_take_while() {
+    let skip_list = SkipList::from_sorted((0..20).map(|k| (k, k * 20)));
+
+    let keys: Vec<_> = skip_list.range_take_while(&5, |_, &v| v < 100).map(|(&k, _)| k).collect();
+    assert_eq!(keys, Vec::<i32>::new());
+    let keys: Vec<_> = skip_list.range_take_while(&2, |_, &v| v < 100).map(|(&k, _)| k).collect();
+    assert_eq!(keys, vec![2, 3, 4]);
+
+    // Stops at the first failure even if later entries pass again
+    let keys: Vec<_> = skip_list.range_take_while(&5, |&k, _| k != 8 && k != 12).map(|(&k, _)| k).collect();
+    assert_eq!(keys, vec![5, 6, 7]);
+
+    // Absent start keys seek to the next one
+    let sparse = SkipList::from_sorted((0..10).map(|k| (k * 10, k)));
+    let keys: Vec<_> = sparse.range_take_while(&25, |_, _| true).map(|(&k, _)| k).collect();
+    assert_eq!(keys, vec![30, 40, 50, 60, 70, 80, 90]);
+    assert_eq!(sparse.range_take_while(&91, |_, _| true).count(), 0);
+
+    // The predicate is not called past the first failure
+    let mut calls = 0;
+    let taken = skip_list.range_take_while(&0, |&k, _| {
+        calls += 1;
+        k < 3
+    }).count();
+    assert_eq!((taken, calls), (3, 4));
+}
+
+#[test]
+fn test_range_find() {
+    let mut skip_list = SkipList::new();
+    for k in 0..100 {
+        skip_list.insert(k, if k < 13 || k % 7 != 0 { 2 * k + 1 } else { k });
+    }
+
+    // 14 is the first key in [10, 50) with an even value
+    assert_eq!(skip_list.range_find(10..50, |_, &v| v % 2 == 0), Some((&14, &14)));
+    assert_eq!(skip_list.range_find(15..21, |_, &v| v % 2 == 0), None);
+    assert_eq!(skip_list.range_find(15..=21, |_, &v| v % 2 == 0), None);
+    assert_eq!(skip_list.range_find(15..=28, |_, &v| v % 2 == 0), Some((&28, &28)));
+
+    // Entries past the match are not tested
+    let mut tested = Vec::new();
+    skip_list.range_find(40..60, |&k, _| {
+        tested.push(k);
+        k == 42
+    });
+    assert_eq!(tested, vec![40, 41, 42]);
+
+    assert_eq!(skip_list.range_find(200.., |_, _| true), None);
+}
+
+#[test]
+fn test_index_entries_mut() {
+    let mut skip_list = build(20);
+
+    let mut page = skip_list.index_entries_mut(5..10);
+    assert_eq!(page.len(), 5);
+    assert_eq!(page.iter().map(|(k, _)| **k).collect::<Vec<_>>(), vec![5, 6, 7, 8, 9]);
+    // Random access, back to front
+    for i in (0..page.len()).rev() {
+        *page[i].1 = -(i as i32);
+    }
+
+    for i in 0..20 {
+        let expected = if (5..10).contains(&i) { 5 - i } else { i * 10 };
+        assert_eq!(skip_list.index(i as usize), Some((&i, &expected)));
+    }
+
+    assert_eq!(skip_list.index_entries_mut(18..100).len(), 2);
+    assert!(skip_list.index_entries_mut(20..).is_empty());
+}
@@ -0,0 +1,169 @@
+use skiplist::SkipList;
+
+#[test]
+fn test_retain() {
+    let mut skip_list = SkipList::new();
+    for i in 0..20 {
+        skip_list.insert(i, i * 10);
+    }
+
+    skip_list.retain(|&k, v| {
+        *v += 1;
+        k % 3 == 0
+    });
+
+    let items: Vec<_> = skip_list.iter().map(|(&k, &v)| (k, v)).collect();
+    let expected: Vec<_> = (0..20).filter(|k| k % 3 == 0).map(|k| (k, k * 10 + 1)).collect();
+    assert_eq!(items, expected);
+    assert_eq!(skip_list.len(), expected.len());
+
+    for (idx, (key, value)) in expected.iter().enumerate() {
+        assert_eq!(skip_list.index(idx), Some((key, value)));
+    }
+}
+
+#[test]
+fn test_retain_indexed_keeps_even_indices() {
+    let mut skip_list = SkipList::new();
+    for i in 0..10 {
+        skip_list.insert(i * 5, i);
+    }
+
+    let mut seen = vec![];
+    skip_list.retain_indexed(|index, _, _| {
+        seen.push(index);
+        index % 2 == 0
+    });
+
+    // Indices are the original positions, not shifted by earlier removals
+    assert_eq!(seen, (0..10).collect::<Vec<_>>());
+
+    let keys: Vec<_> = skip_list.iter().map(|(&k, _)| k).collect();
+    assert_eq!(keys, vec![0, 10, 20, 30, 40]);
+    for (idx, key) in keys.iter().enumerate() {
+        assert_eq!(skip_list.index(idx).map(|(k, _)| k), Some(key));
+    }
+}
+
+#[test]
+fn test_retain_all_and_none() {
+    let mut skip_list = SkipList::new();
+    for i in 0..50 {
+        skip_list.insert(i, i);
+    }
+
+    skip_list.retain(|_, _| true);
+    assert_eq!(skip_list.len(), 50);
+
+    skip_list.retain(|_, _| false);
+    assert!(skip_list.is_empty());
+    assert_eq!(skip_list.index(0), None);
+
+    // Still usable afterwards
+    skip_list.insert(1, 1);
+    assert_eq!(skip_list.index(0), Some((&1, &1)));
+}
+
+#[test]
+fn test_retain_panicking_predicate_leaves_list_valid() {
+    let mut skip_list = SkipList::new();
+    for i in 0..30 {
+        skip_list.insert(i, i);
+    }
+
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        skip_list.retain_indexed(|index, _, _| {
+            if index == 15 {
+                panic!("boom");
+            }
+            index % 2 == 0
+        });
+    }));
+    assert!(result.is_err());
+
+    // Entries visited before the panic were filtered, the rest are kept
+    let expected: Vec<_> = (0..30).filter(|&k| k >= 15 || k % 2 == 0).collect();
+    let keys: Vec<_> = skip_list.iter().map(|(&k, _)| k).collect();
+    assert_eq!(keys, expected);
+    for (idx, key) in expected.iter().enumerate() {
+        assert_eq!(skip_list.index(idx).map(|(k, _)| k), Some(key));
+    }
+}
+
+#[test]
+fn test_retain_returning_odd_keys() {
+    let mut skip_list = SkipList::new();
+    for i in (0..20).rev() {
+        skip_list.insert(i, i.to_string());
+    }
+
+    let removed = skip_list.retain_returning(|&k, _| k % 2 == 0);
+
+    // Removed entries come back in sorted order
+    let expected: Vec<_> = (0..20).filter(|k| k % 2 == 1).map(|k| (k, k.to_string())).collect();
+    assert_eq!(removed, expected);
+
+    let keys: Vec<_> = skip_list.iter().map(|(&k, _)| k).collect();
+    assert_eq!(keys, (0..20).step_by(2).collect::<Vec<_>>());
+    for (i, k) in (0..20).step_by(2).enumerate() {
+        assert_eq!(skip_list.index(i), Some((&k, &k.to_string())));
+    }
+
+    // The closure can still update kept values
+    let removed = skip_list.retain_returning(|_, v| {
+        v.push('!');
+        true
+    });
+    assert!(removed.is_empty());
+    assert_eq!(skip_list.get(&4), Some(&"4!".to_string()));
+
+    let removed = skip_list.retain_returning(|_, _| false);
+    assert_eq!(removed.len(), 10);
+    assert!(skip_list.is_empty());
+}
+
+#[test]
+fn test_retain_count() {
+    let mut skip_list = SkipList::new();
+    for i in (0..20).rev() {
+        skip_list.insert(i, i);
+    }
+
+    assert_eq!(skip_list.retain_count(|&k, _| k % 2 == 0), 10);
+    assert_eq!(skip_list.len(), 10);
+    assert!(skip_list.iter().map(|(&k, _)| k).eq((0..20).step_by(2)));
+    skip_list.assert_invariants();
+
+    // Nothing changed
+    assert_eq!(skip_list.retain_count(|_, _| true), 0);
+    assert_eq!(skip_list.retain_count(|_, _| false), 10);
+    assert!(skip_list.is_empty());
+    assert_eq!(skip_list.retain_count(|_, _| false), 0);
+}
+
+#[test]
+fn test_dedup_by_value() {
+    let mut skip_list = SkipList::new();
+    for (k, v) in [(5, 'a'), (3, 'b'), (1, 'a'), (4, 'b'), (2, 'a')] {
+        skip_list.insert(k, v);
+    }
+
+    skip_list.dedup_by_value();
+    let entries: Vec<_> = skip_list.iter().map(|(&k, &v)| (k, v)).collect();
+    assert_eq!(entries, vec![(1, 'a'), (3, 'b'), (5, 'a')]);
+    skip_list.assert_invariants();
+
+    // Long runs, including one at the end
+    let mut skip_list = SkipList::from_sorted((0..1000).map(|i| (i, i / 7)));
+    skip_list.dedup_by_value();
+    assert_eq!(skip_list.len(), 143);
+    assert!(skip_list.iter().map(|(&k, _)| k).eq((0..1000).step_by(7)));
+    skip_list.assert_invariants();
+
+    // Nothing to remove
+    skip_list.dedup_by_value();
+    assert_eq!(skip_list.len(), 143);
+    let mut empty: SkipList<i32, i32> = SkipList::new();
+    empty.dedup_by_value();
+    assert!(empty.is_empty());
+}
@@ -173,4 +173,503 @@ fn test_span_verification_large_dataset() {
             assert!(skip_list.verify_spans(), "Span verification failed after removing {} elements", i + 1);
         }
     }
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_span_verification_with_entries() {
+    let mut skip_list = SkipList::new();
+
+    for i in [23, 7, 45, 12, 89, 3, 56, 34, 78, 1] {
+        *skip_list.entry(i).or_insert(0) += i;
+        assert!(skip_list.verify_spans(), "Span verification failed after entry insert {}", i);
+    }
+
+    for i in [7, 89, 1, 100] {
+        if let skiplist::Entry::Occupied(entry) = skip_list.entry(i) {
+            entry.remove();
+        }
+        assert!(skip_list.verify_spans(), "Span verification failed after entry remove {}", i);
+    }
+}
+
+#[test]
+fn test_level_of() {
+    let mut skip_list = SkipList::new();
+
+    for (key, level) in [(10, 0), (20, 3), (30, 1), (40, 0), (50, 2)] {
+        skip_list.insert_with_level(key, key, level);
+    }
+    assert!(skip_list.verify_spans());
+
+    assert_eq!(skip_list.level_of(&10), Some(0));
+    assert_eq!(skip_list.level_of(&20), Some(3));
+    assert_eq!(skip_list.level_of(&30), Some(1));
+    assert_eq!(skip_list.level_of(&40), Some(0));
+    assert_eq!(skip_list.level_of(&50), Some(2));
+    assert_eq!(skip_list.level_of(&35), None);
+
+    skip_list.remove(&20);
+    assert_eq!(skip_list.level_of(&20), None);
+    assert_eq!(skip_list.level_of(&50), Some(2));
+}
+
+#[test]
+fn test_span_verification_after_retain() {
+    for modulo in 1..6 {
+        let mut skip_list = SkipList::new();
+        for i in 0..200 {
+            skip_list.insert(i, i);
+        }
+
+        skip_list.retain_indexed(|index, _, _| index % modulo == 0);
+        assert!(skip_list.verify_spans(), "Span verification failed after retain mod {}", modulo);
+        assert_eq!(skip_list.len(), (0..200).filter(|i| i % modulo == 0).count());
+    }
+}
+
+#[test]
+fn test_span_verification_after_bulk_remove() {
+    let mut skip_list = SkipList::new();
+    for i in 0..1000 {
+        skip_list.insert(i, i);
+    }
+
+    let keys: Vec<i32> = (0..1000).step_by(3).collect();
+    assert_eq!(skip_list.bulk_remove(&keys), keys.len());
+    assert!(skip_list.verify_spans());
+}
+
+#[test]
+fn test_span_verification_after_clone() {
+    let mut skip_list = SkipList::new();
+    for i in 0..500 {
+        skip_list.insert((i * 7) % 500, i);
+    }
+
+    let cloned = skip_list.clone();
+    assert!(cloned.verify_spans());
+    for i in 0..500 {
+        assert_eq!(cloned.level_of(&i), skip_list.level_of(&i));
+    }
+}
+
+#[test]
+fn test_from_raw_parts_round_trip() {
+    let mut skip_list = SkipList::new();
+    for i in [23, 7, 45, 12, 89, 3, 56, 34, 78, 1, 99, 67, 21] {
+        skip_list.insert(i, i * 10);
+    }
+
+    let spans = skip_list.dump_spans();
+    let nodes: Vec<_> = skip_list
+        .iter()
+        .map(|(&k, &v)| (k, v, skip_list.level_of(&k).unwrap()))
+        .collect();
+
+    let rebuilt = unsafe { SkipList::from_raw_parts(nodes, spans.clone()) };
+    assert_eq!(rebuilt.dump_spans(), spans);
+    assert!(rebuilt.verify_spans());
+    assert_eq!(rebuilt.format_tower(), skip_list.format_tower());
+
+    // Hand-written layout
+    // L1: HEAD ------> 2 -> TAIL
+    // L0: HEAD -> 1 -> 2 -> TAIL
+    let spans = vec![vec![1, 1, 1], vec![2, 1]];
+    let hand_built = unsafe { SkipList::from_raw_parts(vec![(1, 'a', 0), (2, 'b', 1)], spans.clone()) };
+    assert_eq!(hand_built.dump_spans(), spans);
+    assert_eq!(hand_built.index(1), Some((&2, &'b')));
+
+    let empty: SkipList<i32, i32> = unsafe { SkipList::from_raw_parts(vec![], vec![vec![1]]) };
+    assert!(empty.is_empty());
+    assert!(empty.verify_spans());
+}
+
+#[test]
+fn test_span_verification_with_end_trimming() {
+    let mut skip_list = SkipList::new();
+    for i in 0..20 {
+        skip_list.insert(i, i);
+    }
+
+    assert_eq!(skip_list.remove_first_n(5), 5);
+    assert!(skip_list.verify_spans());
+    assert_eq!(skip_list.remove_last_n(5), 5);
+    assert!(skip_list.verify_spans());
+
+    let keys: Vec<_> = skip_list.iter().map(|(&k, _)| k).collect();
+    assert_eq!(keys, (5..15).collect::<Vec<_>>());
+
+    for n in [0, 1, 3, 7] {
+        let mut front = SkipList::new();
+        let mut back = SkipList::new();
+        for i in 0..200 {
+            front.insert(i, i);
+            back.insert(i, i);
+        }
+        for _ in 0..n * 10 {
+            front.remove_first_n(n);
+            back.remove_last_n(n);
+            assert!(front.verify_spans());
+            assert!(back.verify_spans());
+        }
+    }
+}
+
+#[test]
+fn test_iter_with_spans_level_zero() {
+    let mut skip_list = SkipList::new();
+    for i in (0..300).rev() {
+        skip_list.insert(i, i * 2);
+    }
+    for i in (0..300).step_by(3) {
+        skip_list.remove(&i);
+    }
+    skip_list.remove_first_n(4);
+    skip_list.remove_last_n(4);
+
+    let entries: Vec<_> = skip_list.iter_with_spans().collect();
+    assert_eq!(entries.len(), skip_list.len());
+    assert!(entries.iter().all(|&(_, _, span)| span == 1));
+    assert!(
+        entries
+            .iter()
+            .zip(skip_list.iter())
+            .all(|(&(k, v, _), (ik, iv))| k == ik && v == iv)
+    );
+
+    assert_eq!(SkipList::<i32, i32>::new().iter_with_spans().count(), 0);
+}
+
+#[test]
+fn test_auto_shrink_after_mass_deletion() {
+    use rand::{SeedableRng, rngs::StdRng};
+    use skiplist::SkipListBuilder;
+
+    let mass_delete = |auto_shrink: bool| {
+        let mut skip_list = SkipListBuilder::new()
+            .rng(StdRng::seed_from_u64(11))
+            .build()
+            .unwrap();
+        skip_list.set_auto_shrink(auto_shrink);
+        for i in 0..2000 {
+            skip_list.insert(i, i);
+        }
+
+        // Spare only the four tallest nodes, which keeps the tower tall
+        let mut by_level: Vec<_> = (0..2000).map(|i| (skip_list.level_of(&i).unwrap(), i)).collect();
+        by_level.sort_unstable_by(|a, b| b.cmp(a));
+        let spared: Vec<_> = by_level[..4].iter().map(|&(_, i)| i).collect();
+        for i in 0..2000 {
+            if !spared.contains(&i) {
+                skip_list.remove(&i);
+            }
+        }
+
+        assert!(skip_list.verify_spans());
+        assert_eq!(skip_list.len(), 4);
+        for &i in &spared {
+            assert_eq!(skip_list.get(&i), Some(&i));
+        }
+        // Number of levels in use
+        skip_list.dump_spans().len()
+    };
+
+    assert!(mass_delete(false) >= 9);
+    assert!(mass_delete(true) <= 6);
+}
+
+#[test]
+fn test_auto_shrink_every_removal_path() {
+    // 500 entries where only every 50th node is tall, so removing the rest
+    // leaves at most 10 entries under a tower of 11 levels
+    let build = |auto_shrink: bool| {
+        let mut skip_list = SkipList::new();
+        skip_list.set_auto_shrink(auto_shrink);
+        for i in 0..500 {
+            skip_list.insert_with_level(i, i, if i % 50 == 0 { 10 } else { 0 });
+        }
+        skip_list
+    };
+    let levels = |skip_list: &SkipList<i32, i32>| skip_list.dump_spans().len();
+
+    let remove_all: [fn(&mut SkipList<i32, i32>); 4] = [
+        |skip_list| skip_list.retain(|k, _| k % 50 == 0),
+        |skip_list| {
+            let keys: Vec<_> = (0..500).filter(|k| k % 50 != 0).collect();
+            skip_list.bulk_remove(&keys);
+        },
+        |skip_list| {
+            skip_list.remove_last_n(490);
+        },
+        |skip_list| {
+            skip_list.remove_range(1..500);
+        },
+    ];
+    for remove in remove_all {
+        let mut skip_list = build(false);
+        remove(&mut skip_list);
+        assert_eq!(levels(&skip_list), 11);
+
+        let mut skip_list = build(true);
+        remove(&mut skip_list);
+        assert!(skip_list.verify_spans());
+        assert!(levels(&skip_list) <= 6, "{} levels left", levels(&skip_list));
+        assert_eq!(skip_list.get(&0), Some(&0));
+    }
+}
+
+#[test]
+fn test_shrink_to_fit() {
+    let mut skip_list = SkipList::new();
+    for i in 0..100 {
+        skip_list.insert_with_level(i, i, if i % 10 == 0 { 12 } else { 0 });
+    }
+    assert_eq!(skip_list.dump_spans().len(), 13);
+
+    // ceil(log2(101)) = 7
+    skip_list.shrink_to_fit();
+    assert!(skip_list.verify_spans());
+    assert_eq!(skip_list.dump_spans().len(), 8);
+    assert_eq!(skip_list.level_of(&50), Some(7));
+    assert_eq!(skip_list.level_of(&51), Some(0));
+    for i in 0..100 {
+        assert_eq!(skip_list.index(i as usize), Some((&i, &i)));
+    }
+
+    // Already short enough: nothing changes
+    let before = skip_list.dump_spans();
+    skip_list.shrink_to_fit();
+    assert_eq!(skip_list.dump_spans(), before);
+}
+
+#[test]
+fn test_split_into_chunks() {
+    let mut skip_list = SkipList::new();
+    for i in 0..100 {
+        skip_list.insert(i, i * 2);
+    }
+
+    let chunks = skip_list.split_into_chunks(3);
+    let sizes: Vec<_> = chunks.iter().map(|chunk| chunk.len()).collect();
+    assert_eq!(sizes, vec![34, 33, 33]);
+
+    // Contiguous, disjoint key ranges covering everything in order
+    let mut next_key = 0;
+    for chunk in &chunks {
+        assert!(chunk.verify_spans());
+        let (&min, &max) = chunk.key_bounds().unwrap();
+        assert_eq!(min, next_key);
+        assert_eq!(max - min + 1, chunk.len() as i32);
+        for (i, (&k, &v)) in chunk.iter().enumerate() {
+            assert_eq!(k, min + i as i32);
+            assert_eq!(v, k * 2);
+        }
+        next_key = max + 1;
+    }
+    assert_eq!(next_key, 100);
+
+    // Chunks are independent lists
+    let mut chunks = chunks;
+    chunks[1].insert(1000, 0);
+    chunks[1].remove(&40);
+    assert!(chunks[1].verify_spans());
+    assert_eq!(chunks[2].len(), 33);
+
+    // More chunks than entries
+    let mut small = SkipList::new();
+    for i in 0..3 {
+        small.insert(i, i);
+    }
+    let sizes: Vec<_> = small.split_into_chunks(5).iter().map(|chunk| chunk.len()).collect();
+    assert_eq!(sizes, vec![1, 1, 1, 0, 0]);
+
+    let whole = SkipList::<i32, i32>::new().split_into_chunks(1);
+    assert_eq!(whole.len(), 1);
+    assert!(whole[0].is_empty());
+}
+
+#[test]
+fn test_stats() {
+    let empty = SkipList::<i32, i32>::new().stats();
+    assert_eq!(empty.len, 0);
+    assert_eq!(empty.level, 0);
+    assert_eq!(empty.height, 1);
+    assert_eq!(empty.avg_level, 0.0);
+    assert_eq!(empty.max_span_at_top, 1);
+
+    let mut skip_list = SkipList::new();
+    for (key, level) in [(1, 0), (2, 2), (3, 1), (4, 0), (5, 3)] {
+        skip_list.insert_with_level(key, key, level);
+    }
+
+    let stats = skip_list.stats();
+    assert_eq!(stats.len, 5);
+    assert_eq!(stats.level, 3);
+    assert_eq!(stats.height, 4);
+    assert_eq!(stats.avg_level, 1.2);
+    // Level 3 holds only key 5: head -> 5 spans 5 entries, 5 -> tail spans 1
+    assert_eq!(stats.max_span_at_top, 5);
+    assert!(stats.memory_bytes > empty.memory_bytes);
+
+    // Each extra entry and tower level adds to the estimate
+    skip_list.insert_with_level(6, 6, 1);
+    let grown = skip_list.stats();
+    assert_eq!(grown.max_span_at_top, 5);
+    assert!(grown.memory_bytes > stats.memory_bytes);
+
+    skip_list.remove(&5);
+    let shrunk = skip_list.stats();
+    // Level 2 now holds only key 2, which jumps over 3, 4 and 6 to the tail
+    assert_eq!(shrunk.level, 2);
+    assert_eq!(shrunk.max_span_at_top, 4);
+    assert_eq!(shrunk.avg_level, 0.8);
+}
+
+#[test]
+fn test_assert_invariants_accepts_valid_lists() {
+    let mut skip_list = SkipList::new();
+    skip_list.assert_invariants();
+    for i in 0..300 {
+        skip_list.insert((i * 37) % 211, i);
+        if i % 3 == 0 {
+            skip_list.remove(&((i * 11) % 211));
+        }
+    }
+    skip_list.assert_invariants();
+    skip_list.debug_assert_invariants();
+}
+
+#[test]
+#[should_panic(expected = "level 1, the node at index 1: span is 1, expected 2")]
+fn test_assert_invariants_reports_wrong_span() {
+    // L1: HEAD ------> 2 ------> TAIL, with the second span off by one
+    // L0: HEAD -> 1 -> 2 -> 3 -> TAIL
+    let spans = vec![vec![1, 1, 1, 1], vec![2, 1]];
+    let corrupted =
+        unsafe { SkipList::from_raw_parts(vec![(1, 'a', 0), (2, 'b', 1), (3, 'c', 0)], spans) };
+    corrupted.assert_invariants();
+}
+
+#[test]
+#[should_panic(expected = "the key of the node at index 2 is not greater than the one before it")]
+fn test_assert_invariants_reports_unsorted_keys() {
+    let spans = vec![vec![1, 1, 1, 1]];
+    let corrupted =
+        unsafe { SkipList::from_raw_parts(vec![(1, 'a', 0), (5, 'b', 0), (3, 'c', 0)], spans) };
+    corrupted.assert_invariants();
+}
+
+#[test]
+fn test_structure_eq() {
+    // L2: HEAD ----------------------> 4 ------> TAIL
+    // L1: HEAD ------> 2 ------------> 4 ------> TAIL
+    // L0: HEAD -> 1 -> 2 -> 3 -> 4 -> 5 -> TAIL
+    let nodes = vec![(1, 'a', 0), (2, 'b', 1), (3, 'c', 0), (4, 'd', 2), (5, 'e', 0)];
+    let spans = vec![vec![1, 1, 1, 1, 1, 1], vec![2, 2, 2], vec![4, 2]];
+    let hand_built = unsafe { SkipList::from_raw_parts(nodes.clone(), spans) };
+
+    // Same level sequence, inserted out of order
+    let mut incremental = SkipList::new();
+    for &index in &[3, 0, 4, 1, 2] {
+        let (key, value, level) = nodes[index];
+        incremental.insert_with_level(key, value, level);
+    }
+    assert!(incremental.structure_eq(&hand_built));
+    assert!(hand_built.structure_eq(&incremental));
+
+    // Same contents, different tower
+    let mut other_levels = SkipList::new();
+    for (key, value, _) in nodes.iter().copied() {
+        other_levels.insert_with_level(key, value, if key == 4 { 1 } else { 0 });
+    }
+    assert!(other_levels.iter().eq(hand_built.iter()));
+    assert!(!other_levels.structure_eq(&hand_built));
+
+    // Same tower, different value
+    let mut other_value = SkipList::new();
+    for (key, value, level) in nodes.iter().copied() {
+        other_value.insert_with_level(key, if key == 3 { 'z' } else { value }, level);
+    }
+    assert!(!other_value.structure_eq(&hand_built));
+
+    assert!(SkipList::<i32, i32>::new().structure_eq(&SkipList::new()));
+}
+
+#[test]
+fn test_verify_order() {
+    let mut skip_list = SkipList::new();
+    assert!(skip_list.verify_order());
+    for i in [5, 1, 9, 3, 7] {
+        skip_list.insert_with_level(i, i, (i % 3) as usize);
+        assert!(skip_list.verify_order());
+    }
+    skip_list.remove(&9);
+    assert!(skip_list.verify_order());
+
+    // Valid spans, but the middle key is out of order
+    let spans = vec![vec![1, 1, 1, 1]];
+    let unsorted = unsafe { SkipList::from_raw_parts(vec![(1, 'a', 0), (5, 'b', 0), (3, 'c', 0)], spans) };
+    assert!(unsorted.verify_spans());
+    assert!(!unsorted.verify_order());
+
+    // Equal neighbours are not strictly increasing either
+    let spans = vec![vec![1, 1, 1]];
+    let duplicated = unsafe { SkipList::from_raw_parts(vec![(2, 'a', 0), (2, 'b', 0)], spans) };
+    assert!(!duplicated.verify_order());
+}
+
+#[test]
+fn test_partition() {
+    let skip_list = SkipList::from_sorted((0..20).map(|i| (i, i * 10)));
+
+    let (even, odd) = skip_list.partition(|k, _| k % 2 == 0);
+    assert!(even.verify_spans());
+    assert!(odd.verify_spans());
+    assert!(even.iter().map(|(&k, &v)| (k, v)).eq((0..20).step_by(2).map(|i| (i, i * 10))));
+    assert!(odd.iter().map(|(&k, _)| k).eq((1..20).step_by(2)));
+
+    // Both halves are independent lists
+    let (mut even, mut odd) = (even, odd);
+    even.insert(1, 0);
+    odd.remove(&5);
+    assert!(even.verify_spans());
+    assert!(odd.verify_spans());
+    assert_eq!((even.len(), odd.len()), (11, 9));
+
+    // Everything on one side
+    let (all, none) = even.partition(|_, _| true);
+    assert_eq!(all.len(), 11);
+    assert!(none.is_empty());
+    assert!(none.verify_spans());
+}
+
+#[test]
+fn test_total_span_at_level() {
+    use rand::{Rng, SeedableRng, rngs::StdRng};
+
+    let mut rng = StdRng::seed_from_u64(11);
+    let mut skip_list = SkipList::new();
+
+    for round in 0..50 {
+        for _ in 0..rng.random_range(0..100) {
+            skip_list.insert(rng.random_range(0..2000), round);
+        }
+        for _ in 0..rng.random_range(0..50) {
+            skip_list.remove(&rng.random_range(0..2000));
+        }
+
+        let levels = skip_list.dump_spans().len();
+        for level in 0..levels {
+            assert_eq!(skip_list.total_span_at_level(level), skip_list.len() + 1);
+        }
+    }
+
+    let empty = SkipList::<i32, i32>::new();
+    assert_eq!(empty.total_span_at_level(0), 1);
+}
+
+#[test]
+#[should_panic(expected = "above the top level")]
+fn test_total_span_above_top_level() {
+    SkipList::<i32, i32>::new().total_span_at_level(1);
+}
@@ -0,0 +1,44 @@
+use std::io::{BufReader, ErrorKind};
+
+use skiplist::SkipList;
+
+#[test]
+fn test_text_round_trip() {
+    let skip_list = SkipList::from_sorted((0..10_000u64).map(|i| (i * 7, format!("value {}", i))));
+
+    let mut out = Vec::new();
+    skip_list.write_to(&mut out).unwrap();
+    assert_eq!(out.iter().filter(|&&b| b == b'\n').count(), 10_000);
+    assert!(out.starts_with(b"0\tvalue 0\n7\tvalue 1\n"));
+
+    let decoded = SkipList::<u64, String>::read_from(BufReader::new(&out[..])).unwrap();
+    assert_eq!(decoded.len(), skip_list.len());
+    assert!(decoded.iter().eq(skip_list.iter()));
+
+    // Values may contain tabs, only the first one separates
+    let skip_list = SkipList::from_sorted([(1, "a\tb".to_string())]);
+    let mut out = Vec::new();
+    skip_list.write_to(&mut out).unwrap();
+    let decoded = SkipList::<i32, String>::read_from(&out[..]).unwrap();
+    assert_eq!(decoded.get(&1).map(String::as_str), Some("a\tb"));
+
+    let empty = SkipList::<i32, i32>::read_from(&b""[..]).unwrap();
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn test_read_from_rejects_invalid_lines() {
+    let read = |input: &str| SkipList::<i32, i32>::read_from(input.as_bytes()).unwrap_err();
+
+    for (input, message) in [
+        ("1\t10\n2 20\n", "line 2: expected a tab between key and value"),
+        ("1\t10\nx\t20\n", "line 2: invalid key"),
+        ("1\tten\n", "line 1: invalid value"),
+        ("1\t10\n3\t30\n3\t31\n", "line 3: key is not greater than the one before it"),
+        ("2\t20\n1\t10\n", "line 2: key is not greater than the one before it"),
+    ] {
+        let error = read(input);
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+        assert_eq!(error.to_string(), message);
+    }
+}